@@ -0,0 +1,26 @@
+use std::borrow::Cow;
+
+/// Folds text into pure ASCII, for serial consoles and terminals that can't show anything else.
+/// Box drawing falls back to `-`, `|` and `+`, any other character that isn't ASCII to `?`.
+pub fn to_ascii(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(text.chars().map(to_ascii_char).collect())
+}
+
+fn to_ascii_char(c: char) -> char {
+    match c {
+        c if c.is_ascii() => c,
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' | '╴' | '╶' | '╸' | '╺' | '╼' | '╾' => {
+            '-'
+        }
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' | '╵' | '╷' | '╹' | '╻' | '╽' | '╿' => {
+            '|'
+        }
+        '\u{2500}'..='\u{257f}' => '+',
+        '≈' => '~',
+        _ => '?',
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::command::DirectoryEnum;
+use crate::dir_helpers::{dir_sizes_ordered, get_paths_to_delete, DepthBand};
+use crate::fs_limiter::FsLimiter;
+
+const FILES_PER_FOLDER: usize = 8;
+const FILE_SIZE: usize = 16 * 1024;
+
+/// How fast a synthetic tree was searched and sized.
+#[derive(Debug, Clone, Copy)]
+pub struct Benchmark {
+    pub dir_count: usize,
+    pub size: usize,
+    pub duration: Duration,
+}
+
+impl Benchmark {
+    pub fn dirs_per_sec(&self) -> f64 {
+        self.dir_count as f64 / self.duration.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn mib_per_sec(&self) -> f64 {
+        self.size as f64 / 1024_f64.powi(2) / self.duration.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Creates a tree of `projects` Rust projects inside `parent`, times how long it takes to find
+/// and size their `target` folders, and removes the tree again.
+pub fn run(parent: &Path, projects: usize, limiter: Arc<FsLimiter>) -> io::Result<Benchmark> {
+    let root = parent.join(format!(".cargo-wipe-benchmark-{}", std::process::id()));
+
+    let result = generate(&root, projects).and_then(|dir_count| scan(&root, dir_count, limiter));
+    let removed = fs::remove_dir_all(&root);
+
+    let benchmark = result?;
+    removed?;
+
+    Ok(benchmark)
+}
+
+/// Returns how many folders were created.
+fn generate(root: &Path, projects: usize) -> io::Result<usize> {
+    let mut dir_count = 0;
+
+    for i in 0..projects {
+        let project = root.join(format!("project_{i}"));
+        let target = project.join("target");
+
+        for dir in [
+            project.join("src"),
+            target.join("debug").join("deps"),
+            target.join("debug").join("build"),
+        ] {
+            fs::create_dir_all(&dir)?;
+
+            for j in 0..FILES_PER_FOLDER {
+                fs::write(dir.join(format!("file_{j}")), vec![0; FILE_SIZE])?;
+            }
+        }
+
+        fs::File::create(target.join(".rustc_info.json"))?;
+        dir_count += 6;
+    }
+
+    Ok(dir_count)
+}
+
+fn scan(root: &Path, dir_count: usize, limiter: Arc<FsLimiter>) -> io::Result<Benchmark> {
+    let started = Instant::now();
+
+    let paths = get_paths_to_delete(
+        root,
+        &DirectoryEnum::Target,
+        &[],
+        DepthBand::default(),
+        &[],
+        &limiter,
+        false,
+    )?
+    .into_iter()
+    .map(|path| path.map_err(|e| e.error))
+    .collect::<io::Result<Vec<_>>>()?;
+
+    let mut size = 0;
+    for (_, dir_info, _, _) in dir_sizes_ordered(paths, limiter, 0, false) {
+        size += dir_info?.size;
+    }
+
+    Ok(Benchmark {
+        dir_count,
+        size,
+        duration: started.elapsed(),
+    })
+}
@@ -0,0 +1,14 @@
+use std::fs;
+use std::path::Path;
+
+/// Counts the packages in the `Cargo.lock` of the project a `target` folder belongs to. They are
+/// all compiled again by the next build after the `target` folder is wiped.
+pub fn rebuild_count(target: &Path) -> Option<usize> {
+    let lock = fs::read_to_string(target.parent()?.join("Cargo.lock")).ok()?;
+
+    Some(
+        lock.lines()
+            .filter(|line| line.trim() == "[[package]]")
+            .count(),
+    )
+}
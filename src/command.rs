@@ -1,38 +1,268 @@
+use regex::Regex;
 use std::{fmt, io, path, str};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(bin_name = "cargo")]
 pub enum Command {
-    /// Recursively finds and optionally wipes all <target> or <node_modules> folders that are found in the current path. Add the `-w` flag to wipe all folders found. USE WITH CAUTION!
+    /// Recursively finds and optionally wipes all <target> or <node_modules> folders that are found in the current path. Add the `-w --force` flags to wipe all folders found. USE WITH CAUTION!
     Wipe(Args),
 }
 
-#[derive(Debug, StructOpt)]
+#[derive(Debug, Default, StructOpt)]
 pub struct Args {
-    /// rust | node
-    pub language: LanguageEnum,
-    /// Caution! If set it will wipe all folders found! Unset by default
+    /// rust | node | coverage
+    #[structopt(required_unless = "benchmark")]
+    pub language: Option<LanguageEnum>,
+    /// Caution! If set together with --force it will wipe all folders found! Unset by default
     #[structopt(short, long)]
     pub wipe: bool,
+    /// Confirms --wipe. Without it, --wipe only shows what would be wiped
+    #[structopt(short = "y", long, alias = "yes")]
+    pub force: bool,
     /// Absolute paths to ignore
     #[structopt(short, long, parse(from_os_str))]
     pub ignores: Vec<path::PathBuf>,
+    /// text | xml | json | markdown
+    #[structopt(long, default_value = "text")]
+    pub format: FormatEnum,
+    /// Fail before wiping anything if less than this can be wiped, e.g. 500MiB or 2GiB
+    #[structopt(long)]
+    pub expect_at_least: Option<ByteSize>,
+    /// Protect folders that contain a file with this name, e.g. .nowipe
+    #[structopt(long)]
+    pub skip_if_contains: Vec<String>,
+    /// Before wiping, record the files and sizes of every folder into a manifest in this directory
+    #[structopt(long, parse(from_os_str))]
+    pub manifest: Option<path::PathBuf>,
+    /// Rename the folders to <name>.wipe-pending instead of wiping them
+    #[structopt(long, conflicts_with = "commit-staged")]
+    pub stage: bool,
+    /// Wipe the folders that were previously renamed by --stage
+    #[structopt(long)]
+    pub commit_staged: bool,
+    /// Limit the number of filesystem calls in flight at the same time, e.g. for network mounts
+    #[structopt(long)]
+    pub max_inflight: Option<usize>,
+    /// Show how long it took to size every folder
+    #[structopt(long)]
+    pub timings_per_folder: bool,
+    /// Also wipe the shared store of the pnpm managed node_modules folders
+    #[structopt(long)]
+    pub include_pnpm_store: bool,
+    /// Write a JSON report per project (immediate child of the current path) into this directory
+    #[structopt(long, parse(from_os_str))]
+    pub per_project_report: Option<path::PathBuf>,
+    /// Show which share of the whole current path the folders to wipe take up
+    #[structopt(long)]
+    pub with_root_size: bool,
+    /// Also include the components of every path as an array in the JSON output
+    #[structopt(long)]
+    pub path_components: bool,
+    /// Temporarily make the parents you own writable so that their folders can be wiped
+    #[structopt(long)]
+    pub chmod_parents: bool,
+    /// Also write the summary totals as JSON into this file
+    #[structopt(long, parse(from_os_str))]
+    pub summary_json: Option<path::PathBuf>,
+    /// Also report the size of the files directly inside each folder
+    #[structopt(long)]
+    pub with_shallow: bool,
+    /// Hold a lock on this file while wiping, so that concurrent runs don't collide
+    #[structopt(long, parse(from_os_str))]
+    pub lock: Option<path::PathBuf>,
+    /// Only write ASCII, for terminals that can't show anything else
+    #[structopt(long)]
+    pub ascii: bool,
+    /// Also count the symlinks inside each folder
+    #[structopt(long)]
+    pub show_symlinks: bool,
+    /// Search from the root of the enclosing git repository instead of the current folder
+    #[structopt(long)]
+    pub git_root: bool,
+    /// Search from the current folder when `--git-root` finds no git repository
+    #[structopt(long, requires = "git-root")]
+    pub git_root_fallback: bool,
+    /// Estimate how many dependencies the next build compiles again, from the `Cargo.lock`
+    #[structopt(long)]
+    pub rebuild_cost: bool,
+    /// Sum up sibling folders under their common parent and confirm wiping them all at once
+    #[structopt(long)]
+    pub coalesce_siblings: bool,
+    /// Write what happens as a stream of events instead of the regular output: ndjson
+    #[structopt(long)]
+    pub events: Option<EventsEnum>,
+    /// The number of fractional digits of the sizes shown with units, 2 by default. Also adds
+    /// them as `size_human` to the structured formats
+    #[structopt(long)]
+    pub size_precision: Option<usize>,
+    /// Only find the matches that are at most this many levels below the current folder. With 0
+    /// only a match directly inside it is found, like `./target`, and with 1 also the ones inside
+    /// its sub folders, like `./foo/target`
+    #[structopt(long)]
+    pub max_depth: Option<usize>,
+    /// Only find the matches directly inside the current folder and inside its sub folders, like
+    /// `./target` and `./foo/target`, but not `./foo/bar/target`. The same as `--max-depth 1`
+    #[structopt(long, conflicts_with = "max-depth")]
+    pub no_recurse: bool,
+    /// Skip the folders with files that Git LFS tracks, according to the `.gitattributes`
+    #[structopt(long)]
+    pub exclude_if_under_git_lfs: bool,
+    /// Show the size of each folder in its own unit, instead of in MB
+    #[structopt(long)]
+    pub auto_units: bool,
+    /// Also wipe the caches of Turborepo and Nx, when their configuration is found
+    #[structopt(long)]
+    pub detect_monorepo_tool: bool,
+    /// Use the folders of a report written with `--format json` instead of searching
+    #[structopt(long, parse(from_os_str), conflicts_with = "commit-staged")]
+    pub wipe_from: Option<path::PathBuf>,
+    /// Buffer the output and only flush it after each section, which is the default when it
+    /// doesn't go to a terminal
+    #[structopt(long)]
+    pub buffered: bool,
+    /// Refuse to wipe unless it grows the free space of the volume by at least this ratio, e.g.
+    /// 0.1 for 10%
+    #[structopt(long)]
+    pub min_reclaim_ratio: Option<f64>,
+    /// Show how many folders have been sized while the rows are held back. The structured
+    /// formats get it on stderr
+    #[structopt(long)]
+    pub progress: bool,
+    /// Rename each folder out of the way before wiping it, so that builds never see it half
+    /// wiped. Falls back to wiping in place when it can't be renamed
+    #[structopt(long)]
+    pub atomic: bool,
+    /// Leave alone the folders smaller than this, e.g. 10MiB
+    #[structopt(long)]
+    pub min_size: Option<ByteSize>,
+    /// Only show the first rows of the table, while still wiping and counting all the folders
+    #[structopt(long)]
+    pub head: Option<usize>,
+    /// Show all the rows, even with `--head`
+    #[structopt(long)]
+    pub all: bool,
+    /// Also write the folders as NDJSON shards into this directory, next to a manifest.json
+    /// listing them
+    #[structopt(long, parse(from_os_str))]
+    pub shard_output: Option<path::PathBuf>,
+    /// The most folders a shard of `--shard-output` holds
+    #[structopt(long, default_value = "1000")]
+    pub shard_size: usize,
+    /// A report written with `--format json` earlier, to tell how much each folder grew since
+    #[structopt(long, parse(from_os_str))]
+    pub snapshot: Option<path::PathBuf>,
+    /// Only wipe the folders that grew the most since `--snapshot`
+    #[structopt(long, requires = "snapshot")]
+    pub fastest_growing: Option<usize>,
+    /// Also add the filesystem of each folder, by its mount point, to the structured formats
+    #[structopt(long)]
+    pub with_volume: bool,
+    /// What an empty answer to the confirmation prompts means: yes | no
+    #[structopt(long, default_value = "no")]
+    pub prompt_default: PromptDefaultEnum,
+    /// Skip the folders whose full path matches this regular expression, along with everything
+    /// inside them
+    #[structopt(long)]
+    pub exclude_regex: Vec<PathRegex>,
+    /// Time how fast a synthetic tree is searched and sized in a temporary folder, instead of
+    /// searching the current one
+    #[structopt(long)]
+    pub benchmark: bool,
+    /// The number of projects in the synthetic tree of `--benchmark`
+    #[structopt(long, default_value = "100")]
+    pub benchmark_size: usize,
+    /// Create the synthetic tree of `--benchmark` in this folder instead, e.g. on a network mount
+    #[structopt(long, parse(from_os_str))]
+    pub benchmark_dir: Option<path::PathBuf>,
+    /// A file listing the absolute paths of folders to always keep, one per line
+    #[structopt(long, parse(from_os_str))]
+    pub deny_file: Option<path::PathBuf>,
+    /// Also add when each folder was created and last modified to the structured formats
+    #[structopt(long)]
+    pub with_times: bool,
+    /// After wiping, check that every wiped folder is really gone, failing when one is back
+    #[structopt(long)]
+    pub verify: bool,
+    /// Leave out the matches that are less than this many levels below the current folder, the
+    /// opposite of `--max-depth`
+    #[structopt(long)]
+    pub min_depth: Option<usize>,
+    /// Add up to this many example files of each folder to the structured formats
+    #[structopt(long)]
+    pub sample: Option<usize>,
+    /// Report a panic while searching or sizing a folder as an error for that folder and keep
+    /// going
+    #[structopt(long)]
+    pub continue_on_panic: bool,
+    /// Keep this path, relative to each folder, and wipe everything else inside the folder
+    #[structopt(long, alias = "subpath", parse(from_os_str))]
+    pub keep_inside: Vec<path::PathBuf>,
+    /// Collapse the folders that could not be searched into a single line per kind of error
+    #[structopt(long)]
+    pub concise_errors: bool,
+    /// List every folder that could not be searched, even with `--concise-errors`
+    #[structopt(long)]
+    pub verbose: bool,
+    /// The version of the `--format json` schema: 1 | 2
+    #[structopt(long, default_value = "2")]
+    pub output_version: OutputVersionEnum,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, StructOpt)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, StructOpt)]
 pub enum LanguageEnum {
     #[structopt(name = "node_modules")]
     NodeModules,
     Node,
     Target,
+    #[default]
     Rust,
+    Coverage,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum FormatEnum {
+    #[default]
+    Text,
+    Xml,
+    Json,
+    Markdown,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EventsEnum {
+    Ndjson,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum PromptDefaultEnum {
+    Yes,
+    #[default]
+    No,
 }
 
+/// The version of the JSON schema, so that consumers can pin one while fields get added.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum OutputVersionEnum {
+    /// The fields of the first `--format json`, without any that were added since
+    V1,
+    #[default]
+    V2,
+}
+
+/// A size in bytes. Units are binary, so both `1KB` and `1KiB` are 1024 bytes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ByteSize(pub usize);
+
+/// A regular expression that is matched against full paths.
+#[derive(Debug, Clone)]
+pub struct PathRegex(pub Regex);
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum DirectoryEnum {
     NodeModules,
     Target,
+    Coverage,
 }
 
 impl str::FromStr for LanguageEnum {
@@ -44,9 +274,10 @@ impl str::FromStr for LanguageEnum {
             "node" => Ok(LanguageEnum::Node),
             "target" => Ok(LanguageEnum::Target),
             "rust" => Ok(LanguageEnum::Rust),
+            "coverage" => Ok(LanguageEnum::Coverage),
             _ => Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
-                "Valid options are: rust | node",
+                "Valid options are: rust | node | coverage",
             )),
         }
     }
@@ -59,10 +290,158 @@ impl fmt::Display for LanguageEnum {
             LanguageEnum::NodeModules => write!(f, "node_modules"),
             LanguageEnum::Rust => write!(f, "rust"),
             LanguageEnum::Target => write!(f, "target"),
+            LanguageEnum::Coverage => write!(f, "coverage"),
+        }
+    }
+}
+
+impl str::FromStr for FormatEnum {
+    type Err = io::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().trim() {
+            "text" => Ok(FormatEnum::Text),
+            "xml" => Ok(FormatEnum::Xml),
+            "json" => Ok(FormatEnum::Json),
+            "markdown" => Ok(FormatEnum::Markdown),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Valid options are: text | xml | json | markdown",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for FormatEnum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatEnum::Text => write!(f, "text"),
+            FormatEnum::Xml => write!(f, "xml"),
+            FormatEnum::Json => write!(f, "json"),
+            FormatEnum::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl str::FromStr for EventsEnum {
+    type Err = io::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().trim() {
+            "ndjson" => Ok(EventsEnum::Ndjson),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Valid options are: ndjson",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for EventsEnum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventsEnum::Ndjson => write!(f, "ndjson"),
+        }
+    }
+}
+
+impl str::FromStr for PromptDefaultEnum {
+    type Err = io::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().trim() {
+            "yes" => Ok(PromptDefaultEnum::Yes),
+            "no" => Ok(PromptDefaultEnum::No),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Valid options are: yes | no",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PromptDefaultEnum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PromptDefaultEnum::Yes => write!(f, "yes"),
+            PromptDefaultEnum::No => write!(f, "no"),
         }
     }
 }
 
+impl str::FromStr for OutputVersionEnum {
+    type Err = io::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "1" => Ok(OutputVersionEnum::V1),
+            "2" => Ok(OutputVersionEnum::V2),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Valid options are: 1 | 2",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputVersionEnum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputVersionEnum::V1 => write!(f, "1"),
+            OutputVersionEnum::V2 => write!(f, "2"),
+        }
+    }
+}
+
+impl str::FromStr for ByteSize {
+    type Err = io::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+
+        let multiplier = match unit.trim().to_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kb" | "kib" => 1024,
+            "m" | "mb" | "mib" => 1024_usize.pow(2),
+            "g" | "gb" | "gib" => 1024_usize.pow(3),
+            "t" | "tb" | "tib" => 1024_usize.pow(4),
+            _ => 0,
+        };
+
+        match number.parse::<f64>() {
+            Ok(number) if multiplier > 0 => Ok(ByteSize((number * multiplier as f64) as usize)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Valid sizes look like: 1024 | 500KiB | 1.5GiB",
+            )),
+        }
+    }
+}
+
+impl str::FromStr for PathRegex {
+    type Err = regex::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Regex::new(value).map(PathRegex)
+    }
+}
+
+impl PartialEq for PathRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl PathRegex {
+    pub fn is_match(&self, path: &path::Path) -> bool {
+        self.0.is_match(&path.to_string_lossy())
+    }
+}
+
 impl From<LanguageEnum> for DirectoryEnum {
     fn from(language: LanguageEnum) -> Self {
         match language {
@@ -70,6 +449,7 @@ impl From<LanguageEnum> for DirectoryEnum {
             LanguageEnum::NodeModules => DirectoryEnum::NodeModules,
             LanguageEnum::Rust => DirectoryEnum::Target,
             LanguageEnum::Target => DirectoryEnum::Target,
+            LanguageEnum::Coverage => DirectoryEnum::Coverage,
         }
     }
 }
@@ -79,6 +459,19 @@ impl fmt::Display for DirectoryEnum {
         match self {
             DirectoryEnum::NodeModules => write!(f, "node_modules"),
             DirectoryEnum::Target => write!(f, "target"),
+            DirectoryEnum::Coverage => write!(f, "coverage"),
+        }
+    }
+}
+
+impl DirectoryEnum {
+    /// The names of the folders to search for. The test coverage tools of the different
+    /// ecosystems all name their output differently.
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            DirectoryEnum::NodeModules => &["node_modules"],
+            DirectoryEnum::Target => &["target"],
+            DirectoryEnum::Coverage => &["coverage", ".nyc_output", "htmlcov"],
         }
     }
 }
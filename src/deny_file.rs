@@ -0,0 +1,21 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Reads the paths to always keep, one per line. Blank lines and lines starting with `#` are
+/// left out.
+pub fn read(file: &Path) -> io::Result<HashSet<PathBuf>> {
+    Ok(fs::read_to_string(file)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| normalize(Path::new(line)))
+        .collect())
+}
+
+/// Resolves symlinks and relative components, so that the same folder always compares equal.
+/// Paths that don't exist are kept as they are.
+pub fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
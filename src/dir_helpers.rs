@@ -1,15 +1,26 @@
 use num_format::{Locale, ToFormattedString};
 use number_prefix::NumberPrefix;
-use std::path::PathBuf;
-use std::{fs, io};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use std::{fs, io, thread};
 
-use crate::command::DirectoryEnum;
+use crate::command::{DirectoryEnum, PathRegex};
+use crate::fs_limiter::FsLimiter;
+use crate::pnpm;
 
 #[derive(Debug, Copy, Clone)]
 pub struct DirInfo {
     pub dir_count: usize,
     pub file_count: usize,
     pub size: usize,
+    /// The size of the files directly inside the folder, without its sub folders
+    pub shallow_size: usize,
+    pub symlink_count: usize,
 }
 
 impl DirInfo {
@@ -18,6 +29,8 @@ impl DirInfo {
             dir_count,
             file_count,
             size,
+            shallow_size: 0,
+            symlink_count: 0,
         }
     }
 
@@ -31,48 +44,176 @@ impl DirInfo {
     }
 
     pub fn size_formatted_flex(&self) -> String {
+        self.size_formatted_flex_precision(2)
+    }
+
+    /// Same as `size_formatted_flex`, with `precision` fractional digits.
+    pub fn size_formatted_flex_precision(&self, precision: usize) -> String {
         let np = NumberPrefix::binary(self.size as f64);
 
         match np {
-            NumberPrefix::Prefixed(prefix, n) => format!("{n:.2} {prefix}B"),
+            NumberPrefix::Prefixed(prefix, n) => format!("{n:.precision$} {prefix}B"),
             NumberPrefix::Standalone(bytes) => format!("{bytes} bytes"),
         }
     }
 }
 
-fn is_valid_target(path: PathBuf, directory: &DirectoryEnum) -> bool {
+fn is_valid_target(path: PathBuf, directory: &DirectoryEnum, limiter: &FsLimiter) -> bool {
     if directory == &DirectoryEnum::Target {
         let file_path = path.join(".rustc_info.json");
-        return file_path.exists();
+        return limiter.run(|| file_path.exists());
     }
 
     true
 }
 
-pub type PathsResult = io::Result<Vec<Result<String, io::Error>>>;
+/// Whether `path` is still a folder that a search for `directory` would find.
+pub fn is_valid_match(path: &Path, directory: &DirectoryEnum, limiter: &FsLimiter) -> bool {
+    path.file_name()
+        .is_some_and(|name| directory.names().contains(&&*name.to_string_lossy()))
+        && limiter.run(|| path.is_dir())
+        && is_valid_target(path.to_path_buf(), directory, limiter)
+}
 
-pub fn get_paths_to_delete(path: impl Into<PathBuf>, directory: &DirectoryEnum) -> PathsResult {
-    fn walk(dir: io::Result<fs::ReadDir>, directory: &DirectoryEnum) -> PathsResult {
-        let mut dir = match dir {
+/// An error that occurred while processing a specific path.
+#[derive(Debug)]
+pub struct PathError {
+    pub path: String,
+    pub error: io::Error,
+}
+
+pub type PathsResult = io::Result<Vec<Result<String, PathError>>>;
+
+/// How many levels below the searched folder the matches may be, where a match directly inside
+/// it is at depth 0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DepthBand {
+    /// Shallower matches are left out
+    pub min: usize,
+    /// Deeper folders are not searched
+    pub max: Option<usize>,
+}
+
+impl DepthBand {
+    pub fn new(min: Option<usize>, max: Option<usize>) -> Self {
+        Self {
+            min: min.unwrap_or_default(),
+            max,
+        }
+    }
+}
+
+/// Finds the folders to wipe, named after one of the names of `directory` or one of the
+/// `extra_names`, within the `depths` below `path`. Folders matching any of `exclude` are neither
+/// matched nor searched.
+pub fn get_paths_to_delete(
+    path: impl Into<PathBuf>,
+    directory: &DirectoryEnum,
+    extra_names: &[&str],
+    depths: DepthBand,
+    exclude: &[PathRegex],
+    limiter: &FsLimiter,
+    catch_panics: bool,
+) -> PathsResult {
+    find_paths(
+        path.into(),
+        directory,
+        depths,
+        exclude,
+        limiter,
+        catch_panics,
+        &|file_name| directory.names().contains(&file_name) || extra_names.contains(&file_name),
+    )
+}
+
+/// Finds the folders that were previously renamed by `stage_path`.
+pub fn get_staged_paths(
+    path: impl Into<PathBuf>,
+    directory: &DirectoryEnum,
+    depths: DepthBand,
+    exclude: &[PathRegex],
+    limiter: &FsLimiter,
+    catch_panics: bool,
+) -> PathsResult {
+    find_paths(
+        path.into(),
+        directory,
+        depths,
+        exclude,
+        limiter,
+        catch_panics,
+        &|file_name| {
+            directory
+                .names()
+                .iter()
+                .any(|name| is_staged_name(file_name, name))
+        },
+    )
+}
+
+/// Walks the folders below `path`, looking for the ones `is_match` accepts. When `catch_panics`
+/// is set, a panic while walking one of the sub folders is reported as an error for it and the
+/// walk carries on with the others.
+pub fn find_paths(
+    path: PathBuf,
+    directory: &DirectoryEnum,
+    depths: DepthBand,
+    exclude: &[PathRegex],
+    limiter: &FsLimiter,
+    catch_panics: bool,
+    is_match: &dyn Fn(&str) -> bool,
+) -> PathsResult {
+    Search {
+        directory,
+        depths,
+        exclude,
+        limiter,
+        catch_panics,
+        is_match,
+    }
+    .walk(path, 0)
+}
+
+/// What stays the same all along a walk of `find_paths`.
+struct Search<'a> {
+    directory: &'a DirectoryEnum,
+    depths: DepthBand,
+    exclude: &'a [PathRegex],
+    limiter: &'a FsLimiter,
+    catch_panics: bool,
+    is_match: &'a dyn Fn(&str) -> bool,
+}
+
+impl Search<'_> {
+    fn walk(&self, path: PathBuf, depth: usize) -> PathsResult {
+        let mut dir = match self.limiter.run(|| fs::read_dir(&path)) {
             Ok(dir) => dir,
-            Err(e) => {
-                return Ok(vec![Err(e)]);
+            Err(error) => {
+                return Ok(vec![Err(PathError {
+                    path: path.display().to_string(),
+                    error,
+                })]);
             }
         };
 
         dir.try_fold(
             Vec::new(),
-            |mut acc: Vec<Result<String, io::Error>>, file| {
+            |mut acc: Vec<Result<String, PathError>>, file| {
                 let file = file?;
 
-                let size = match file.metadata() {
-                    Ok(data) if data.is_dir() => {
-                        if file.file_name() == directory.to_string()[..] {
-                            if is_valid_target(file.path(), directory) {
+                let size = match self.limiter.run(|| file.metadata()) {
+                    Ok(data)
+                        if data.is_dir()
+                            && !self.exclude.iter().any(|e| e.is_match(&file.path())) =>
+                    {
+                        if (self.is_match)(&file.file_name().to_string_lossy()) {
+                            if depth >= self.depths.min
+                                && is_valid_target(file.path(), self.directory, self.limiter)
+                            {
                                 acc.push(Ok(file.path().display().to_string()));
                             }
-                        } else {
-                            acc.append(&mut walk(fs::read_dir(file.path()), directory)?);
+                        } else if self.depths.max.is_none_or(|max_depth| depth < max_depth) {
+                            acc.append(&mut self.walk_subtree(file.path(), depth + 1)?);
                         }
                         acc
                     }
@@ -84,11 +225,257 @@ pub fn get_paths_to_delete(path: impl Into<PathBuf>, directory: &DirectoryEnum)
         )
     }
 
-    walk(fs::read_dir(path.into()), directory)
+    fn walk_subtree(&self, path: PathBuf, depth: usize) -> PathsResult {
+        if !self.catch_panics {
+            return self.walk(path, depth);
+        }
+
+        let subtree = path.display().to_string();
+
+        panic::catch_unwind(AssertUnwindSafe(|| self.walk(path, depth))).unwrap_or_else(|payload| {
+            Ok(vec![Err(PathError {
+                path: subtree,
+                error: panic_error(payload.as_ref()),
+            })])
+        })
+    }
+}
+
+pub const STAGED_SUFFIX: &str = ".wipe-pending";
+
+/// `target.wipe-pending` and `target.wipe-pending.2` are both staged names for `target`.
+fn is_staged_name(file_name: &str, name: &str) -> bool {
+    match file_name
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix(STAGED_SUFFIX))
+    {
+        Some("") => true,
+        Some(counter) => counter
+            .strip_prefix('.')
+            .is_some_and(|c| !c.is_empty() && c.chars().all(|c| c.is_ascii_digit())),
+        None => false,
+    }
+}
+
+/// Renames the folder so that it can be wiped later on, appending a counter if the staged name
+/// is already taken.
+pub fn stage_path(path: impl Into<PathBuf>) -> io::Result<PathBuf> {
+    let path = path.into();
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "nothing to stage"))?
+        .to_string_lossy()
+        .to_string();
+
+    let mut staged = path.with_file_name(format!("{file_name}{STAGED_SUFFIX}"));
+    let mut counter = 1;
+
+    while staged.exists() {
+        counter += 1;
+        staged = path.with_file_name(format!("{file_name}{STAGED_SUFFIX}.{counter}"));
+    }
+
+    fs::rename(&path, &staged)?;
+
+    Ok(staged)
+}
+
+/// Renames the folder next to itself under a name that no search matches, so that it is gone
+/// from its original path at once, however long it takes to wipe afterwards.
+pub fn rename_aside(path: impl Into<PathBuf>) -> io::Result<PathBuf> {
+    let path = path.into();
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "nothing to rename"))?
+        .to_string_lossy()
+        .to_string();
+    let pid = std::process::id();
+
+    let mut renamed = path.with_file_name(format!("{file_name}.wiping.{pid}"));
+    let mut counter = 1;
+
+    while renamed.exists() {
+        counter += 1;
+        renamed = path.with_file_name(format!("{file_name}.wiping.{pid}.{counter}"));
+    }
+
+    fs::rename(&path, &renamed)?;
+
+    Ok(renamed)
+}
+
+/// Wipes the folder. Removing it needs write access to its parent, so a parent that isn't writable
+/// is reported as such or, if `chmod_parent` is set, made writable for the duration of the removal.
+pub fn remove_dir(path: impl Into<PathBuf>, chmod_parent: bool) -> io::Result<()> {
+    let path = path.into();
+
+    let Some(parent) = path.parent().filter(|parent| !is_writable(parent)) else {
+        return fs::remove_dir_all(&path);
+    };
+
+    let not_writable = |e: io::Error| match e.kind() {
+        io::ErrorKind::PermissionDenied => io::Error::new(
+            e.kind(),
+            format!("parent directory not writable: {}", parent.display()),
+        ),
+        _ => e,
+    };
+
+    if chmod_parent {
+        // only its owner can change it, so not the folder of another user
+        let permissions = fs::metadata(parent)?.permissions();
+        fs::set_permissions(parent, writable(&permissions)).map_err(not_writable)?;
+        let result = fs::remove_dir_all(&path);
+        fs::set_permissions(parent, permissions)?;
+
+        return result;
+    }
+
+    fs::remove_dir_all(&path).map_err(not_writable)
+}
+
+/// Wipes everything inside the folder, except the `kept` paths relative to it and the folders
+/// leading to them.
+pub fn remove_dir_except(path: &Path, kept: &[PathBuf]) -> io::Result<()> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let name = PathBuf::from(entry.file_name());
+
+        if kept.contains(&name) {
+            continue;
+        }
+
+        let inner = kept_below(&name, kept);
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() && !inner.is_empty() {
+            remove_dir_except(&entry.path(), &inner)?;
+        } else if file_type.is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether anything besides the `kept` paths relative to it is left inside the folder.
+pub fn has_leftovers(path: &Path, kept: &[PathBuf]) -> io::Result<bool> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let name = PathBuf::from(entry.file_name());
+
+        if kept.contains(&name) {
+            continue;
+        }
+
+        let inner = kept_below(&name, kept);
+
+        if inner.is_empty() || !entry.file_type()?.is_dir() {
+            return Ok(true);
+        }
+        if has_leftovers(&entry.path(), &inner)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
 }
 
-pub fn dir_size(path: impl Into<PathBuf>) -> io::Result<DirInfo> {
-    fn walk(dir: io::Result<fs::ReadDir>) -> io::Result<DirInfo> {
+/// The `kept` paths that are inside `name`, relative to it.
+fn kept_below(name: &Path, kept: &[PathBuf]) -> Vec<PathBuf> {
+    kept.iter()
+        .filter_map(|kept| kept.strip_prefix(name).ok())
+        .filter(|inner| !inner.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+/// The size of the `kept` paths relative to the folder, which wiping it leaves behind.
+pub fn kept_size(path: &Path, kept: &[PathBuf], limiter: &FsLimiter) -> usize {
+    kept.iter()
+        .map(|kept| path.join(kept))
+        .filter_map(|kept| match fs::symlink_metadata(&kept) {
+            Ok(data) if data.is_dir() => dir_size(kept, limiter).ok().map(|i| i.size),
+            Ok(data) => Some(data.len() as usize),
+            Err(_) => None,
+        })
+        .sum()
+}
+
+#[cfg(unix)]
+fn writable(permissions: &fs::Permissions) -> fs::Permissions {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::Permissions::from_mode(permissions.mode() | 0o200)
+}
+
+#[cfg(not(unix))]
+fn writable(permissions: &fs::Permissions) -> fs::Permissions {
+    permissions.clone()
+}
+
+/// Whether the current user may create and remove entries in the folder, which isn't the same as
+/// its permissions having a write bit. Anything else that fails is left for the removal to report.
+#[cfg(unix)]
+fn is_writable(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return true;
+    };
+
+    let writable = unsafe { libc::access(path.as_ptr(), libc::W_OK) } == 0;
+
+    writable || io::Error::last_os_error().kind() != io::ErrorKind::PermissionDenied
+}
+
+#[cfg(not(unix))]
+fn is_writable(path: &Path) -> bool {
+    fs::metadata(path).map_or(true, |data| !data.permissions().readonly())
+}
+
+pub fn dir_size(path: impl Into<PathBuf>, limiter: &FsLimiter) -> io::Result<DirInfo> {
+    dir_size_with(path, limiter, &mut |_, _| true)
+}
+
+/// Sizes a folder that was found, taking its layout into account.
+pub fn folder_size(path: impl Into<PathBuf>, limiter: &FsLimiter) -> io::Result<DirInfo> {
+    folder_size_visiting(path, limiter, &mut |_| ())
+}
+
+/// Same as `folder_size`, but `visit` is also called with the path of every file that is counted.
+pub fn folder_size_visiting(
+    path: impl Into<PathBuf>,
+    limiter: &FsLimiter,
+    visit: &mut impl FnMut(&Path),
+) -> io::Result<DirInfo> {
+    let path = path.into();
+
+    if pnpm::is_pnpm_layout(&path) {
+        pnpm::dir_size_visiting(path, limiter, visit)
+    } else {
+        dir_size_with(path, limiter, &mut |file, _| {
+            visit(file);
+            true
+        })
+    }
+}
+
+/// Same as `dir_size`, but `visit` is also called with the path and the metadata of every file.
+/// Files for which `visit` returns `false` are not counted.
+pub fn dir_size_with(
+    path: impl Into<PathBuf>,
+    limiter: &FsLimiter,
+    visit: &mut impl FnMut(&Path, &fs::Metadata) -> bool,
+) -> io::Result<DirInfo> {
+    fn walk(
+        dir: io::Result<fs::ReadDir>,
+        limiter: &FsLimiter,
+        visit: &mut dyn FnMut(&Path, &fs::Metadata) -> bool,
+    ) -> io::Result<DirInfo> {
         let mut dir = match dir {
             Ok(dir) => dir,
             Err(_) => {
@@ -99,21 +486,161 @@ pub fn dir_size(path: impl Into<PathBuf>) -> io::Result<DirInfo> {
         dir.try_fold(DirInfo::new(0, 0, 0), |acc, file| {
             let file = file?;
 
-            let size = match file.metadata() {
-                Ok(data) if data.is_dir() => walk(fs::read_dir(file.path()))?,
-                Ok(data) => DirInfo::new(1, 1, data.len() as usize),
-                _ => DirInfo::new(0, 0, 0),
+            let metadata = limiter.run(|| file.metadata());
+            let symlink_count = match &metadata {
+                Ok(data) if data.is_symlink() => 1,
+                _ => 0,
+            };
+
+            let (size, shallow_size) = match metadata {
+                Ok(data) if data.is_dir() => (
+                    walk(limiter.run(|| fs::read_dir(file.path())), limiter, visit)?,
+                    0,
+                ),
+                Ok(data) if visit(&file.path(), &data) => {
+                    (DirInfo::new(1, 1, data.len() as usize), data.len() as usize)
+                }
+                _ => (DirInfo::new(0, 0, 0), 0),
             };
 
-            Ok(DirInfo::new(
-                acc.dir_count + 1,
-                acc.file_count + size.file_count,
-                acc.size + size.size,
-            ))
+            Ok(DirInfo {
+                dir_count: acc.dir_count + 1,
+                file_count: acc.file_count + size.file_count,
+                size: acc.size + size.size,
+                shallow_size: acc.shallow_size + shallow_size,
+                symlink_count: acc.symlink_count + size.symlink_count + symlink_count,
+            })
         })
     }
 
-    walk(fs::read_dir(path.into()))
+    let path = path.into();
+
+    walk(limiter.run(|| fs::read_dir(path)), limiter, visit)
+}
+
+/// The index of the path, its size, how long sizing it took and the sampled files.
+pub type SizedPath = (usize, io::Result<DirInfo>, Duration, Vec<String>);
+
+/// What a worker hands back for one path, or the payload of its panic.
+type Sized = (
+    usize,
+    thread::Result<(io::Result<DirInfo>, Duration, Vec<String>)>,
+);
+
+/// Sizes the paths on a pool of threads, yielding the results in the same order as the paths
+/// were given, each one as soon as it and all the ones before it are done. Up to `samples` of the
+/// files inside each folder are picked up along the way, relative to the folder.
+pub struct OrderedSizes {
+    receiver: mpsc::Receiver<Sized>,
+    pending: BTreeMap<usize, Sized>,
+    next: usize,
+    catch_panics: bool,
+}
+
+pub fn dir_sizes_ordered(
+    paths: Vec<String>,
+    limiter: Arc<FsLimiter>,
+    samples: usize,
+    catch_panics: bool,
+) -> OrderedSizes {
+    sizes_ordered(paths, catch_panics, move |path| {
+        let mut sampled = Vec::new();
+        let dir_info = folder_size_visiting(path, &limiter, &mut |file| {
+            if sampled.len() < samples {
+                let file = file.strip_prefix(path).unwrap_or(file);
+                sampled.push(file.display().to_string());
+            }
+        });
+
+        (dir_info, sampled)
+    })
+}
+
+/// Same as `dir_sizes_ordered`, with `size` doing the sizing of every path. When `catch_panics`
+/// is set, a panic while sizing one path is reported as an error for that path and the others
+/// carry on, otherwise it's raised again once that path is reached.
+pub fn sizes_ordered<F>(paths: Vec<String>, catch_panics: bool, size: F) -> OrderedSizes
+where
+    F: Fn(&str) -> (io::Result<DirInfo>, Vec<String>) + Send + Sync + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let threads = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(paths.len());
+
+    let paths = Arc::new(paths);
+    let next_path = Arc::new(AtomicUsize::new(0));
+    let size = Arc::new(size);
+
+    for _ in 0..threads {
+        let sender = sender.clone();
+        let paths = Arc::clone(&paths);
+        let next_path = Arc::clone(&next_path);
+        let size = Arc::clone(&size);
+
+        thread::spawn(move || loop {
+            let i = next_path.fetch_add(1, Ordering::Relaxed);
+            let Some(path) = paths.get(i) else {
+                break;
+            };
+
+            let sized = panic::catch_unwind(AssertUnwindSafe(|| {
+                let started = Instant::now();
+                let (dir_info, sampled) = size(path);
+
+                (dir_info, started.elapsed(), sampled)
+            }));
+
+            if sender.send((i, sized)).is_err() {
+                break;
+            }
+        });
+    }
+
+    OrderedSizes {
+        receiver,
+        pending: BTreeMap::new(),
+        next: 0,
+        catch_panics,
+    }
+}
+
+/// The error a caught panic is reported as.
+fn panic_error(payload: &(dyn Any + Send)) -> io::Error {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    };
+
+    io::Error::other(format!("panicked: {message}"))
+}
+
+impl Iterator for OrderedSizes {
+    type Item = SizedPath;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((i, sized)) = self.pending.remove(&self.next) {
+                self.next += 1;
+
+                return Some(match sized {
+                    Ok((dir_info, duration, sampled)) => (i, dir_info, duration, sampled),
+                    Err(payload) if self.catch_panics => {
+                        let error = panic_error(payload.as_ref());
+                        (i, Err(error), Duration::ZERO, Vec::new())
+                    }
+                    Err(payload) => panic::resume_unwind(payload),
+                });
+            }
+
+            let sized = self.receiver.recv().ok()?;
+            self.pending.insert(sized.0, sized);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -130,8 +657,37 @@ mod tests {
             dir_count: 0,
             file_count: 0,
             size,
+            shallow_size: 0,
+            symlink_count: 0,
         };
 
         assert_eq!(di.size_formatted_flex(), output);
     }
+
+    #[parameterized(
+        precision = { 0, 1, 2, 3 },
+        output = { "2 MiB", "1.5 MiB", "1.52 MiB", "1.523 MiB" },
+    )]
+    fn size_formatted_flex_precision(precision: usize, output: &str) {
+        let di = DirInfo::new(0, 0, 1_597_000);
+
+        assert_eq!(di.size_formatted_flex_precision(precision), output);
+    }
+
+    #[parameterized(
+        file_name = {
+            "target.wipe-pending",
+            "target.wipe-pending.2",
+            "target.wipe-pending.12",
+            "target",
+            "target.wipe-pending.",
+            "target.wipe-pending.x",
+            "target.wipe-pendingx",
+            "node_modules.wipe-pending",
+        },
+        expected = { true, true, true, false, false, false, false, false },
+    )]
+    fn staged_name(file_name: &str, expected: bool) {
+        assert_eq!(is_staged_name(file_name, "target"), expected);
+    }
 }
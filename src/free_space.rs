@@ -0,0 +1,34 @@
+use std::io;
+use std::path::Path;
+
+/// The space left on the volume that holds `path`, in bytes, as far as an unprivileged user can
+/// use it.
+#[cfg(unix)]
+pub fn available(path: &Path) -> io::Result<usize> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok((stat.f_bavail as u64 * stat.f_frsize as u64) as usize)
+}
+
+#[cfg(not(unix))]
+pub fn available(_path: &Path) -> io::Result<usize> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the free space can't be queried on this platform",
+    ))
+}
+
+/// Wiping `reclaimable` bytes has to grow the `free` space by at least `min_ratio`, e.g. 0.1 for
+/// 10%, for the wipe to be worth it.
+pub fn is_worth_it(reclaimable: usize, free: usize, min_ratio: f64) -> bool {
+    reclaimable as f64 >= free as f64 * min_ratio
+}
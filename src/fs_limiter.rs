@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+
+/// Bounds the number of filesystem calls that are in flight at the same time, across all the
+/// threads sharing it. Fragile mounts such as NFS don't cope well with stampedes.
+#[derive(Debug, Default)]
+pub struct FsLimiter {
+    max_inflight: Option<usize>,
+    inflight: Mutex<usize>,
+    released: Condvar,
+    peak: AtomicUsize,
+}
+
+impl FsLimiter {
+    pub fn new(max_inflight: Option<usize>) -> Self {
+        Self {
+            max_inflight: max_inflight.map(|max| max.max(1)),
+            ..Self::default()
+        }
+    }
+
+    /// Runs `f` once there is room for one more call in flight.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _slot = self.acquire();
+
+        f()
+    }
+
+    fn acquire(&self) -> Slot<'_> {
+        let mut inflight = self
+            .inflight
+            .lock()
+            .expect("the lock should never be poisoned");
+
+        if let Some(max_inflight) = self.max_inflight {
+            while *inflight >= max_inflight {
+                inflight = self
+                    .released
+                    .wait(inflight)
+                    .expect("the lock should never be poisoned");
+            }
+        }
+
+        *inflight += 1;
+        self.peak.fetch_max(*inflight, Ordering::Relaxed);
+
+        Slot(self)
+    }
+
+    /// The highest number of calls that were ever in flight at the same time.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+/// A call in flight, which gives its room back when dropped, even if the call panicked.
+struct Slot<'a>(&'a FsLimiter);
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        *self
+            .0
+            .inflight
+            .lock()
+            .expect("the lock should never be poisoned") -= 1;
+        self.0.released.notify_one();
+    }
+}
@@ -0,0 +1,9 @@
+use std::path::{Path, PathBuf};
+
+/// Walks up from `path` to the nearest folder with a `.git` in it. That is a folder for a regular
+/// repository and a file for worktrees and submodules.
+pub fn repo_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf)
+}
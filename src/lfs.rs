@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::{Component, Path};
+
+/// Whether Git LFS tracks files inside `folder`, according to the `.gitattributes` files from
+/// `repo_root` down to the folder. Such build outputs are committed on purpose.
+pub fn is_tracked(folder: &Path, repo_root: &Path) -> bool {
+    folder
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(repo_root))
+        .any(|dir| {
+            let Ok(attributes) = fs::read_to_string(dir.join(".gitattributes")) else {
+                return false;
+            };
+            let Ok(relative) = folder.strip_prefix(dir) else {
+                return false;
+            };
+            let components = relative
+                .components()
+                .filter_map(|c| match c {
+                    Component::Normal(c) => Some(c.to_string_lossy().to_string()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            tracks_folder(&attributes, &components)
+        })
+}
+
+/// Whether one of the LFS patterns of a `.gitattributes` file is about the folder.
+fn tracks_folder(attributes: &str, components: &[String]) -> bool {
+    lfs_patterns(attributes).any(|pattern| matches_folder(pattern, components))
+}
+
+fn lfs_patterns(attributes: &str) -> impl Iterator<Item = &str> {
+    attributes.lines().filter_map(|line| {
+        let mut fields = line.split_whitespace();
+        let pattern = fields.next().filter(|p| !p.starts_with('#'))?;
+
+        fields
+            .any(|attribute| attribute == "filter=lfs")
+            .then_some(pattern)
+    })
+}
+
+/// Patterns track files, so a pattern is about the folder its files are in, like `build` for
+/// `build/**` or `build/*.wasm`. Patterns without a folder, like `*.psd`, can match files
+/// anywhere and are left out.
+fn matches_folder(pattern: &str, components: &[String]) -> bool {
+    let pattern = pattern.trim_start_matches('/');
+    let base = match pattern.strip_suffix("/**") {
+        Some(base) => base,
+        None => match pattern.rsplit_once('/') {
+            Some((base, _)) => base,
+            None => return false,
+        },
+    };
+
+    // the folder is inside a tracked one
+    let inside = (1..=components.len()).any(|i| glob_match(base, &components[..i].join("/")));
+
+    // or a tracked one is inside the folder
+    let relative = components.join("/");
+    let contains = !base.contains(['*', '?', '['])
+        && base
+            .strip_prefix(&relative)
+            .is_some_and(|rest| rest.starts_with('/'));
+
+    inside || contains
+}
+
+/// `*` and `?` don't match a `/`, `**` matches anything.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[u8], text: &[u8]) -> bool {
+        match pattern {
+            [] => text.is_empty(),
+            [b'*', b'*', rest @ ..] => {
+                let rest = rest.strip_prefix(b"/").unwrap_or(rest);
+                (0..=text.len()).any(|i| matches(rest, &text[i..]))
+            }
+            [b'*', rest @ ..] => (0..=text.len())
+                .take_while(|&i| i == 0 || text[i - 1] != b'/')
+                .any(|i| matches(rest, &text[i..])),
+            [b'?', rest @ ..] => matches!(text, [c, ..] if *c != b'/') && matches(rest, &text[1..]),
+            [c, rest @ ..] => matches!(text, [t, ..] if t == c) && matches(rest, &text[1..]),
+        }
+    }
+
+    matches(pattern.as_bytes(), text.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(
+        pattern = { "build", "build", "*", "b*d", "b?ild", "**/build", "**/build", "a/**/build", "*" },
+        text = { "build", "builds", "build", "build", "build", "build", "x/y/build", "a/b/c/build", "a/build" },
+        expected = { true, false, true, true, true, true, true, true, false },
+    )]
+    fn glob(pattern: &str, text: &str, expected: bool) {
+        assert_eq!(glob_match(pattern, text), expected);
+    }
+}
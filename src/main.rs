@@ -1,9 +1,26 @@
-use std::io::stdout;
+use std::io::{stdout, BufWriter, Write};
 use structopt::StructOpt;
+use yansi::Paint;
 
+pub mod ascii;
+pub mod benchmark;
+pub mod cargo_lock;
 pub mod command;
+pub mod deny_file;
 pub mod dir_helpers;
+pub mod free_space;
+pub mod fs_limiter;
+pub mod git;
+pub mod lfs;
+pub mod manifest;
+pub mod monorepo;
+pub mod pnpm;
+pub mod report;
+pub mod times;
+pub mod volume;
 pub mod wipe;
+pub mod wipe_lock;
+pub mod wsl;
 
 use crate::command::Command;
 use crate::wipe::{Wipe, WipeParams};
@@ -18,7 +35,32 @@ fn main() -> anyhow::Result<()> {
     match command {
         Command::Wipe(args) => {
             let params = WipeParams::new(&args)?;
-            Wipe::new(&mut stdout, &params).run()?;
+
+            // keep the structured formats free of escape sequences, whatever ends up in them
+            if params.structured() {
+                Paint::disable();
+            }
+
+            let exit_code = if params.buffered {
+                let mut stdout = BufWriter::new(stdout.lock());
+                let exit_code = {
+                    let mut wipe = Wipe::new(&mut stdout, &params);
+                    wipe.run()?;
+                    wipe.exit_code()
+                };
+                stdout.flush()?;
+
+                exit_code
+            } else {
+                let mut wipe = Wipe::new(&mut stdout, &params);
+                wipe.run()?;
+
+                wipe.exit_code()
+            };
+
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
         }
     }
 
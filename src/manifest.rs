@@ -0,0 +1,56 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::dir_helpers::dir_size_with;
+use crate::fs_limiter::FsLimiter;
+
+/// Records the relative path and the size of every file inside `folder` into a manifest file
+/// placed in `manifest_dir`. Only the listing is recorded, not the contents.
+pub fn write_manifest(
+    manifest_dir: &Path,
+    folder: &Path,
+    limiter: &FsLimiter,
+) -> io::Result<PathBuf> {
+    fs::create_dir_all(manifest_dir)?;
+
+    let name = folder
+        .display()
+        .to_string()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    // the readable part alone is the same for e.g. `a/b_c/target` and `a/b/c/target`
+    let manifest_path = manifest_dir.join(format!(
+        "{}-{:016x}.manifest",
+        name.trim_matches('_'),
+        fnv1a(folder.display().to_string().as_bytes())
+    ));
+
+    let mut entries = Vec::new();
+    dir_size_with(folder, limiter, &mut |path, data| {
+        let path = path.strip_prefix(folder).unwrap_or(path);
+        entries.push((path.display().to_string(), data.len()));
+
+        true
+    })?;
+    entries.sort();
+
+    let mut file = io::BufWriter::new(fs::File::create(&manifest_path)?);
+    writeln!(file, "# {}", folder.display())?;
+
+    for (path, size) in entries {
+        writeln!(file, "{size}\t{path}")?;
+    }
+
+    file.flush()?;
+
+    Ok(manifest_path)
+}
+
+/// A hash that stays the same across runs and Rust versions, so manifests keep their names.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,17 @@
+use std::path::Path;
+
+/// The cache folders of the JavaScript monorepo tools configured in `root`, to wipe along with
+/// the `node_modules` folders. Nx also caches in `node_modules/.cache/nx`, which goes with its
+/// `node_modules` anyway.
+pub fn cache_names(root: &Path) -> Vec<&'static str> {
+    let mut names = Vec::new();
+
+    if root.join("turbo.json").is_file() {
+        names.push(".turbo");
+    }
+    if root.join("nx.json").is_file() {
+        names.push(".nx");
+    }
+
+    names
+}
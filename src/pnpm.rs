@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::dir_helpers::{dir_size_with, DirInfo};
+use crate::fs_limiter::FsLimiter;
+
+/// pnpm keeps the actual packages of a `node_modules` in a `.pnpm` folder, hard linked from a
+/// shared content-addressable store, and only symlinks them into place.
+pub fn is_pnpm_layout(path: &Path) -> bool {
+    path.join(".pnpm").is_dir()
+}
+
+/// Sizes a pnpm `node_modules`, without counting the symlinks as files and counting the files
+/// that are hard linked more than once only once.
+pub fn dir_size(path: impl Into<PathBuf>, limiter: &FsLimiter) -> io::Result<DirInfo> {
+    dir_size_visiting(path, limiter, &mut |_| ())
+}
+
+/// Same as `dir_size`, but `visit` is also called with the path of every file that is counted.
+pub fn dir_size_visiting(
+    path: impl Into<PathBuf>,
+    limiter: &FsLimiter,
+    visit: &mut impl FnMut(&Path),
+) -> io::Result<DirInfo> {
+    let mut seen = HashSet::new();
+
+    dir_size_with(path, limiter, &mut |file, data| {
+        let counted = !data.is_symlink() && is_first_link(&mut seen, data);
+        if counted {
+            visit(file);
+        }
+
+        counted
+    })
+}
+
+#[cfg(unix)]
+fn is_first_link(seen: &mut HashSet<(u64, u64)>, data: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    data.nlink() < 2 || seen.insert((data.dev(), data.ino()))
+}
+
+#[cfg(not(unix))]
+fn is_first_link(_seen: &mut HashSet<(u64, u64)>, _data: &fs::Metadata) -> bool {
+    true
+}
+
+/// Whether `path` looks like a pnpm store, such as `~/.local/share/pnpm/store/v3`, with the
+/// packages in its `files` folder. The store is outside of the search, so this is checked before
+/// a `storeDir` is trusted.
+pub fn is_store(path: &Path, limiter: &FsLimiter) -> bool {
+    let versioned = path.file_name().is_some_and(|name| {
+        name.to_string_lossy()
+            .strip_prefix('v')
+            .is_some_and(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+    });
+
+    versioned && limiter.run(|| path.join("files").is_dir())
+}
+
+/// Reads the location of the shared store from the `.modules.yaml` that pnpm writes into every
+/// `node_modules` it manages.
+pub fn store_dir(node_modules: &Path) -> Option<PathBuf> {
+    let modules = fs::read_to_string(node_modules.join(".modules.yaml")).ok()?;
+
+    modules.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("storeDir:")?.trim();
+        let value = value.trim_matches(|c| c == '\'' || c == '"');
+
+        (!value.is_empty()).then(|| PathBuf::from(value))
+    })
+}
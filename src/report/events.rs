@@ -0,0 +1,47 @@
+use serde::Serialize;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::report::{ErrorReport, FolderReport, SummaryReport};
+
+/// Something that happened during the run, written as soon as it happens so that a progress UI
+/// can follow along.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Event {
+    Found(Box<FolderReport>),
+    Deleted {
+        path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        staged: Option<String>,
+    },
+    Error(ErrorReport),
+    Summary(SummaryReport),
+}
+
+#[derive(Serialize)]
+struct TimestampedEvent<'a> {
+    timestamp_ms: u64,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+/// Writes the event as a single line of JSON.
+pub fn write_ndjson(event: &Event, mut writer: impl io::Write) -> io::Result<()> {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+
+    serde_json::to_writer(
+        &mut writer,
+        &TimestampedEvent {
+            timestamp_ms,
+            event,
+        },
+    )?;
+    writeln!(writer)?;
+    writer.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::io;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::command::OutputVersionEnum;
+use crate::report::{ErrorReport, Report, SkipReason, SummaryReport, Totals};
+
+/// The latest schema, which is the report itself.
+#[derive(Serialize)]
+struct ReportV2<'a> {
+    format_version: u32,
+    #[serde(flatten)]
+    report: &'a Report,
+}
+
+/// The first schema, which `--output-version 1` keeps writing.
+#[derive(Serialize)]
+struct ReportV1<'a> {
+    format_version: u32,
+    path: &'a str,
+    directory: &'a str,
+    wipe: bool,
+    folders: Vec<FolderV1<'a>>,
+    summary: SummaryV1,
+    errors: &'a [ErrorReport],
+}
+
+#[derive(Serialize)]
+struct FolderV1<'a> {
+    path: &'a str,
+    file_count: Option<usize>,
+    size: Option<usize>,
+    skipped: Option<SkipReasonV1>,
+    error: Option<&'a str>,
+}
+
+/// The reasons of the first schema. The ones added since are written as the closest of these, so
+/// that a skipped folder never reads as one that is wiped.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum SkipReasonV1 {
+    Ignored,
+    Protected,
+}
+
+impl From<SkipReason> for SkipReasonV1 {
+    fn from(reason: SkipReason) -> Self {
+        match reason {
+            SkipReason::Protected | SkipReason::Lfs | SkipReason::Denied => Self::Protected,
+            SkipReason::Ignored
+            | SkipReason::Declined
+            | SkipReason::Stale
+            | SkipReason::ChangedType
+            | SkipReason::TooSmall
+            | SkipReason::SlowerGrowth => Self::Ignored,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SummaryV1 {
+    total: Totals,
+    ignored: Totals,
+    wipe: Totals,
+    after: Totals,
+}
+
+impl<'a> From<&'a Report> for ReportV1<'a> {
+    fn from(report: &'a Report) -> Self {
+        let summary = &report.summary;
+
+        Self {
+            format_version: 1,
+            path: &report.path,
+            directory: &report.directory,
+            wipe: report.wipe,
+            folders: report
+                .folders
+                .iter()
+                .map(|folder| FolderV1 {
+                    path: &folder.path,
+                    file_count: folder.file_count,
+                    size: folder.size,
+                    skipped: folder.skipped.map(SkipReasonV1::from),
+                    error: folder.error.as_deref(),
+                })
+                .collect(),
+            summary: SummaryV1 {
+                total: summary.total,
+                ignored: summary.ignored,
+                wipe: summary.wipe,
+                after: summary.after,
+            },
+            errors: &report.errors,
+        }
+    }
+}
+
+pub fn write(
+    report: &Report,
+    version: OutputVersionEnum,
+    mut writer: impl io::Write,
+) -> io::Result<()> {
+    match version {
+        OutputVersionEnum::V1 => {
+            serde_json::to_writer_pretty(&mut writer, &ReportV1::from(report))?;
+        }
+        OutputVersionEnum::V2 => {
+            let report = ReportV2 {
+                format_version: 2,
+                report,
+            };
+            serde_json::to_writer_pretty(&mut writer, &report)?;
+        }
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+pub fn write_summary(summary: &SummaryReport, mut writer: impl io::Write) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut writer, summary)?;
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct SavedReport {
+    folders: Vec<SavedFolder>,
+}
+
+#[derive(Deserialize)]
+struct SavedFolder {
+    path: String,
+    size: Option<usize>,
+    skipped: Option<String>,
+}
+
+/// Reads the folders that were going to be wiped from a report written with `--format json`.
+pub fn read_paths(reader: impl io::Read) -> io::Result<Vec<String>> {
+    let report: SavedReport = serde_json::from_reader(reader)?;
+
+    Ok(report
+        .folders
+        .into_iter()
+        .filter(|folder| folder.skipped.is_none())
+        .map(|folder| folder.path)
+        .collect())
+}
+
+/// Reads the size of every folder of a report written with `--format json`.
+pub fn read_sizes(reader: impl io::Read) -> io::Result<HashMap<String, usize>> {
+    let report: SavedReport = serde_json::from_reader(reader)?;
+
+    Ok(report
+        .folders
+        .into_iter()
+        .filter_map(|folder| Some((folder.path, folder.size?)))
+        .collect())
+}
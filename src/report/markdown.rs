@@ -0,0 +1,62 @@
+use std::io;
+
+use crate::dir_helpers::DirInfo;
+use crate::report::{FolderReport, Report};
+
+/// Writes the folders as a Markdown table, to paste into issues and pull requests. The sizes have
+/// `size_precision` fractional digits.
+pub fn write(report: &Report, size_precision: usize, mut writer: impl io::Write) -> io::Result<()> {
+    writeln!(writer, "| Files | Size | Path |")?;
+    writeln!(writer, "| ----: | ---: | :--- |")?;
+
+    for folder in &report.folders {
+        writeln!(
+            writer,
+            "| {} | {} | {} |",
+            folder
+                .file_count
+                .map(|file_count| DirInfo::new(0, file_count, 0).file_count_formatted())
+                .unwrap_or_else(|| "?".to_string()),
+            folder
+                .size
+                .map(|size| DirInfo::new(0, 0, size).size_formatted_flex_precision(size_precision))
+                .unwrap_or_else(|| "?".to_string()),
+            path_cell(folder),
+        )?;
+    }
+
+    let summary = &report.summary;
+    let wipe = DirInfo::new(0, summary.wipe.file_count, summary.wipe.size);
+    let total = DirInfo::new(0, summary.total.file_count, summary.total.size);
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "**{}:** {} files, {} of the {} in {}",
+        if report.wipe { "Wiped" } else { "Can wipe" },
+        wipe.file_count_formatted(),
+        wipe.size_formatted_flex_precision(size_precision),
+        total.size_formatted_flex_precision(size_precision),
+        escape(&report.path),
+    )?;
+
+    Ok(())
+}
+
+fn path_cell(folder: &FolderReport) -> String {
+    let mut cell = escape(&folder.path);
+
+    if let Some(skipped) = folder.skipped {
+        cell.push_str(&format!(" ({skipped})"));
+    }
+    if let Some(error) = &folder.error {
+        cell.push_str(&format!(" ({})", escape(error)));
+    }
+
+    cell
+}
+
+/// A `|` would end the table cell early.
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
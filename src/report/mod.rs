@@ -0,0 +1,317 @@
+use serde::Serialize;
+use std::path::{Component, Path};
+use std::{fmt, io};
+
+use crate::command::{FormatEnum, OutputVersionEnum};
+use crate::dir_helpers::DirInfo;
+
+pub mod events;
+pub mod json;
+pub mod markdown;
+pub mod per_project;
+pub mod shards;
+pub mod xml;
+
+/// A folder that was found during the search.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct FolderReport {
+    pub path: String,
+    /// The name of the folder, which tells apart the matches of searches for several names
+    pub matched_name: String,
+    pub file_count: Option<usize>,
+    pub size: Option<usize>,
+    /// What wiping the folder frees, without the paths kept by `--keep-inside`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reclaim_bytes: Option<usize>,
+    pub skipped: Option<SkipReason>,
+    pub error: Option<String>,
+    /// Whether the folder passed all the filters and checks, so it is wiped or would be
+    pub reclaimable: bool,
+    /// Why the folder is not reclaimable
+    pub skip_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staged: Option<String>,
+    pub in_git_repo: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_repo_root: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_duration_ms: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_components: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_human: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shallow_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symlink_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rebuild_count: Option<usize>,
+    /// The mount point of the filesystem that holds the folder
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<String>,
+    /// When the folder itself was created, in ISO 8601
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// When the folder itself was last modified, in ISO 8601
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// How many bytes the folder grew since `--snapshot`, the whole size if it's new
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub growth: Option<i64>,
+    /// Whether the folder was really gone after wiping it, only with `--verify`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    /// Some of the files inside the folder, relative to it, only with `--sample`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<Vec<String>>,
+    /// Only for the folders of a `--wipe-from` run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub still_present: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub still_valid: Option<bool>,
+}
+
+impl FolderReport {
+    /// Sums up the outcome of the filters and checks in `reclaimable` and `skip_reason`.
+    pub fn settle(&mut self) {
+        self.skip_reason = match (&self.skipped, &self.error) {
+            (Some(skipped), _) => Some(skipped.to_string()),
+            (None, Some(error)) => Some(error.clone()),
+            (None, None) if self.size.is_none() => Some("unsized".to_string()),
+            (None, None) => None,
+        };
+        self.reclaimable = self.skip_reason.is_none();
+    }
+}
+
+/// Why a folder that was found is not going to be wiped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    Ignored,
+    Protected,
+    /// The confirmation prompt was not answered with yes
+    Declined,
+    /// Git LFS tracks files inside the folder
+    Lfs,
+    /// A folder from `--wipe-from` that is gone or is not a match anymore
+    Stale,
+    /// Something else than a folder was found at the path when it was about to be wiped
+    ChangedType,
+    /// The folder is smaller than `--min-size`
+    TooSmall,
+    /// Other folders grew faster since `--snapshot`
+    SlowerGrowth,
+    /// The folder is listed in `--deny-file`
+    Denied,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Ignored => write!(f, "ignored"),
+            SkipReason::Protected => write!(f, "protected"),
+            SkipReason::Declined => write!(f, "declined"),
+            SkipReason::Lfs => write!(f, "lfs"),
+            SkipReason::Stale => write!(f, "stale"),
+            SkipReason::ChangedType => write!(f, "changed_type"),
+            SkipReason::TooSmall => write!(f, "too_small"),
+            SkipReason::SlowerGrowth => write!(f, "slower_growth"),
+            SkipReason::Denied => write!(f, "denied"),
+        }
+    }
+}
+
+/// Something that went wrong along the way, without stopping the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorReport {
+    pub path: String,
+    pub phase: ErrorPhase,
+    pub message: String,
+    #[serde(skip)]
+    pub kind: io::ErrorKind,
+}
+
+/// The walk errors of one kind, which `--concise-errors` collapses into a single line.
+#[derive(Debug)]
+pub struct ErrorGroup<'a> {
+    pub kind: io::ErrorKind,
+    pub errors: Vec<&'a ErrorReport>,
+}
+
+impl fmt::Display for ErrorGroup<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.errors.len();
+        let noun = if count == 1 {
+            "directory"
+        } else {
+            "directories"
+        };
+
+        write!(f, "{count} {noun} skipped ({})", self.kind)
+    }
+}
+
+/// Groups the walk errors by their kind, in the order each kind was first seen.
+pub fn group_walk_errors(errors: &[ErrorReport]) -> Vec<ErrorGroup<'_>> {
+    let mut groups: Vec<ErrorGroup> = Vec::new();
+
+    for error in errors.iter().filter(|e| e.phase == ErrorPhase::Walk) {
+        match groups.iter_mut().find(|group| group.kind == error.kind) {
+            Some(group) => group.errors.push(error),
+            None => groups.push(ErrorGroup {
+                kind: error.kind,
+                errors: vec![error],
+            }),
+        }
+    }
+
+    groups
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorPhase {
+    Walk,
+    Size,
+    Delete,
+}
+
+impl fmt::Display for ErrorPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorPhase::Walk => write!(f, "walk"),
+            ErrorPhase::Size => write!(f, "size"),
+            ErrorPhase::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+/// The last component of the path, which is the name the search matched.
+pub fn matched_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Splits the path into its named components, leaving out the root and the prefix.
+pub fn path_components(path: &str) -> Vec<String> {
+    Path::new(path)
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(c) => Some(c.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    pub file_count: usize,
+    pub size: usize,
+}
+
+impl From<DirInfo> for Totals {
+    fn from(info: DirInfo) -> Self {
+        Self {
+            file_count: info.file_count,
+            size: info.size,
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryReport {
+    pub total: Totals,
+    pub ignored: Totals,
+    pub wipe: Totals,
+    pub after: Totals,
+    /// The size of the folders to wipe
+    pub found_bytes: usize,
+    /// The size of the folders that were actually wiped, which failed deletions leave out
+    pub reclaimed_bytes: usize,
+    /// How many folders failed to be wiped
+    pub failed_count: usize,
+    pub status: Status,
+    /// The exit code of the process, which follows from the `status`
+    pub exit_code: i32,
+    /// How many of the wiped folders were still present after wiping, only with `--verify`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lingering: Option<usize>,
+    /// How many of the folders from `--wipe-from` were stale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale: Option<usize>,
+    /// What is wiped from the shared pnpm stores outside of the searched folder, which the other
+    /// totals leave out, only with `--include-pnpm-store`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pnpm_store: Option<Totals>,
+}
+
+/// How the run went, as a whole.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// Everything that was found was wiped
+    Ok,
+    /// Folders were found, but not wiped
+    Found,
+    /// Some of the folders failed to be wiped
+    PartialFailure,
+    /// Some of the wiped folders were still present when verifying
+    Lingering,
+    /// Nothing was found
+    #[default]
+    Empty,
+}
+
+impl Status {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Status::Ok | Status::Found | Status::PartialFailure | Status::Empty => 0,
+            Status::Lingering => 3,
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Found => write!(f, "found"),
+            Status::PartialFailure => write!(f, "partial_failure"),
+            Status::Lingering => write!(f, "lingering"),
+            Status::Empty => write!(f, "empty"),
+        }
+    }
+}
+
+/// The outcome of a run, shared by all the structured output formats.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub path: String,
+    pub directory: String,
+    pub wipe: bool,
+    /// What the folders are sorted by, so that the output of two runs can be diffed
+    pub sort_applied: String,
+    pub folders: Vec<FolderReport>,
+    pub summary: SummaryReport,
+    pub errors: Vec<ErrorReport>,
+}
+
+impl Report {
+    pub fn write(
+        &self,
+        format: FormatEnum,
+        version: OutputVersionEnum,
+        size_precision: usize,
+        writer: impl io::Write,
+    ) -> io::Result<()> {
+        match format {
+            FormatEnum::Text => unreachable!("the text format is written by Wipe"),
+            FormatEnum::Xml => xml::write(self, writer),
+            FormatEnum::Json => json::write(self, version, writer),
+            FormatEnum::Markdown => markdown::write(self, size_precision, writer),
+        }
+    }
+}
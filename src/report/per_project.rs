@@ -0,0 +1,67 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::report::{FolderReport, Report, Totals};
+
+/// The folders found inside one of the immediate children of the searched path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectReport {
+    pub project: String,
+    pub folders: Vec<FolderReport>,
+    pub total: Totals,
+}
+
+/// Groups the folders by project. Folders that are immediate children of the searched path
+/// themselves don't belong to any project.
+pub fn group(report: &Report) -> BTreeMap<PathBuf, ProjectReport> {
+    let root = Path::new(&report.path);
+    let mut projects = BTreeMap::<PathBuf, ProjectReport>::new();
+
+    for folder in &report.folders {
+        let Ok(relative) = Path::new(&folder.path).strip_prefix(root) else {
+            continue;
+        };
+
+        let mut components = relative.components();
+        let (Some(project), Some(_)) = (components.next(), components.next()) else {
+            continue;
+        };
+
+        let project = root.join(project);
+        let entry = projects
+            .entry(project.clone())
+            .or_insert_with(|| ProjectReport {
+                project: project.display().to_string(),
+                folders: Vec::new(),
+                total: Totals::default(),
+            });
+
+        entry.total.file_count += folder.file_count.unwrap_or_default();
+        entry.total.size += folder.size.unwrap_or_default();
+        entry.folders.push(folder.clone());
+    }
+
+    projects
+}
+
+/// Writes one `<project>.json` file per project into `dir`.
+pub fn write(report: &Report, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for (project, project_report) in group(report) {
+        let name = project
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let mut file = io::BufWriter::new(fs::File::create(dir.join(format!("{name}.json")))?);
+        serde_json::to_writer_pretty(&mut file, &project_report)?;
+        io::Write::flush(&mut file)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::report::{Report, SummaryReport};
+
+/// Lists the shards written next to it, for the consumers to pick them up in parallel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShardsManifest {
+    pub path: String,
+    pub directory: String,
+    pub wipe: bool,
+    pub folder_count: usize,
+    pub shards: Vec<String>,
+    pub summary: SummaryReport,
+}
+
+/// Writes the folders as `part-0001.ndjson`, `part-0002.ndjson` and so on into `dir`, with at
+/// most `shard_size` folders each, plus a `manifest.json` referencing them.
+pub fn write(report: &Report, dir: &Path, shard_size: usize) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let mut shards = Vec::new();
+
+    for (i, folders) in report.folders.chunks(shard_size.max(1)).enumerate() {
+        let name = format!("part-{:04}.ndjson", i + 1);
+        let mut file = io::BufWriter::new(fs::File::create(dir.join(&name))?);
+
+        for folder in folders {
+            serde_json::to_writer(&mut file, folder)?;
+            writeln!(file)?;
+        }
+
+        file.flush()?;
+        shards.push(name);
+    }
+
+    let manifest = ShardsManifest {
+        path: report.path.clone(),
+        directory: report.directory.clone(),
+        wipe: report.wipe,
+        folder_count: report.folders.len(),
+        shards,
+        summary: report.summary,
+    };
+
+    let mut file = io::BufWriter::new(fs::File::create(dir.join("manifest.json"))?);
+    serde_json::to_writer_pretty(&mut file, &manifest)?;
+    writeln!(file)?;
+    file.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use quick_xml::events::{BytesDecl, Event};
+use quick_xml::Writer;
+use std::io;
+
+use crate::report::{Report, Totals};
+
+pub fn write(report: &Report, writer: impl io::Write) -> io::Result<()> {
+    let mut writer = Writer::new_with_indent(writer, b' ', 2);
+
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    writer
+        .create_element("wipe")
+        .with_attribute(("path", report.path.as_str()))
+        .with_attribute(("directory", report.directory.as_str()))
+        .with_attribute(("wipe", report.wipe.to_string().as_str()))
+        .with_attribute(("sort_applied", report.sort_applied.as_str()))
+        .write_inner_content(|writer| {
+            for folder in &report.folders {
+                let mut element = writer
+                    .create_element("folder")
+                    .with_attribute(("path", folder.path.as_str()))
+                    .with_attribute(("matched_name", folder.matched_name.as_str()));
+
+                if let Some(file_count) = folder.file_count {
+                    element = element.with_attribute(("files", file_count.to_string().as_str()));
+                }
+                if let Some(size) = folder.size {
+                    element = element.with_attribute(("bytes", size.to_string().as_str()));
+                }
+                if let Some(reclaim_bytes) = folder.reclaim_bytes {
+                    element = element
+                        .with_attribute(("reclaim_bytes", reclaim_bytes.to_string().as_str()));
+                }
+                if let Some(size_human) = &folder.size_human {
+                    element = element.with_attribute(("size_human", size_human.as_str()));
+                }
+                if let Some(skipped) = folder.skipped {
+                    element = element.with_attribute(("skipped", skipped.to_string().as_str()));
+                }
+                if let Some(error) = &folder.error {
+                    element = element.with_attribute(("error", error.as_str()));
+                }
+                element = element
+                    .with_attribute(("reclaimable", folder.reclaimable.to_string().as_str()));
+                if let Some(skip_reason) = &folder.skip_reason {
+                    element = element.with_attribute(("skip_reason", skip_reason.as_str()));
+                }
+                if let Some(verified) = folder.verified {
+                    element = element.with_attribute(("verified", verified.to_string().as_str()));
+                }
+                if let Some(staged) = &folder.staged {
+                    element = element.with_attribute(("staged", staged.as_str()));
+                }
+                element = element
+                    .with_attribute(("in_git_repo", folder.in_git_repo.to_string().as_str()));
+                if let Some(git_repo_root) = &folder.git_repo_root {
+                    element = element.with_attribute(("git_repo_root", git_repo_root.as_str()));
+                }
+                if let Some(shallow_size) = folder.shallow_size {
+                    element = element
+                        .with_attribute(("shallow_bytes", shallow_size.to_string().as_str()));
+                }
+                if let Some(symlink_count) = folder.symlink_count {
+                    element =
+                        element.with_attribute(("symlinks", symlink_count.to_string().as_str()));
+                }
+                if let Some(rebuild_count) = folder.rebuild_count {
+                    element = element
+                        .with_attribute(("rebuild_count", rebuild_count.to_string().as_str()));
+                }
+                if let Some(created) = &folder.created {
+                    element = element.with_attribute(("created", created.as_str()));
+                }
+                if let Some(modified) = &folder.modified {
+                    element = element.with_attribute(("modified", modified.as_str()));
+                }
+                if let Some(volume) = &folder.volume {
+                    element = element.with_attribute(("volume", volume.as_str()));
+                }
+                if let Some(size_duration_ms) = folder.size_duration_ms {
+                    element = element.with_attribute((
+                        "size_duration_ms",
+                        size_duration_ms.to_string().as_str(),
+                    ));
+                }
+
+                element.write_empty()?;
+            }
+
+            for error in &report.errors {
+                writer
+                    .create_element("error")
+                    .with_attribute(("path", error.path.as_str()))
+                    .with_attribute(("phase", error.phase.to_string().as_str()))
+                    .with_attribute(("message", error.message.as_str()))
+                    .write_empty()?;
+            }
+
+            let mut summary = writer
+                .create_element("summary")
+                .with_attribute((
+                    "found_bytes",
+                    report.summary.found_bytes.to_string().as_str(),
+                ))
+                .with_attribute((
+                    "reclaimed_bytes",
+                    report.summary.reclaimed_bytes.to_string().as_str(),
+                ))
+                .with_attribute((
+                    "failed_count",
+                    report.summary.failed_count.to_string().as_str(),
+                ))
+                .with_attribute(("status", report.summary.status.to_string().as_str()))
+                .with_attribute(("exit_code", report.summary.exit_code.to_string().as_str()));
+            if let Some(lingering) = report.summary.lingering {
+                summary = summary.with_attribute(("lingering", lingering.to_string().as_str()));
+            }
+            if let Some(stale) = report.summary.stale {
+                summary = summary.with_attribute(("stale", stale.to_string().as_str()));
+            }
+
+            summary.write_inner_content(|writer| {
+                for (name, totals) in [
+                    ("total", &report.summary.total),
+                    ("ignored", &report.summary.ignored),
+                    ("wipe", &report.summary.wipe),
+                    ("after", &report.summary.after),
+                ] {
+                    write_totals(writer, name, totals)?;
+                }
+                if let Some(pnpm_store) = &report.summary.pnpm_store {
+                    write_totals(writer, "pnpm_store", pnpm_store)?;
+                }
+
+                Ok(())
+            })?;
+
+            Ok(())
+        })?;
+
+    writeln!(writer.get_mut())?;
+
+    Ok(())
+}
+
+fn write_totals<W: io::Write>(
+    writer: &mut Writer<W>,
+    name: &str,
+    totals: &Totals,
+) -> io::Result<()> {
+    writer
+        .create_element(name)
+        .with_attribute(("files", totals.file_count.to_string().as_str()))
+        .with_attribute(("bytes", totals.size.to_string().as_str()))
+        .write_empty()?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use parameterized::parameterized;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::ascii::to_ascii;
+use crate::command::LanguageEnum;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+#[parameterized(
+    text = { "1.50 MiB", "├── café", "│  └─ ═╪═", "naïve 🦀" },
+    expected = { "1.50 MiB", "+-- caf?", "|  +- -+-", "na?ve ?" }
+)]
+fn to_ascii_text(text: &str, expected: &str) {
+    assert_eq!(to_ascii(text), expected);
+}
+
+#[parameterized(ascii = { false, true })]
+fn run_with_ascii(ascii: bool) {
+    let language = LanguageEnum::NodeModules;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    let unicode = test_run.path.join("prøjëct─🦀").join("node_modules");
+    std::fs::create_dir_all(&unicode).unwrap();
+    std::fs::write(unicode.join("index.js"), [0; 100]).unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        ascii,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    if ascii {
+        assert!(buff.get_ref().iter().all(|b| *b <= 0x7F));
+        assert!(output.contains("pr?j?ct-?"));
+    } else {
+        assert!(output.contains("prøjëct─🦀"));
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::benchmark;
+use crate::command::LanguageEnum;
+use crate::fs_limiter::FsLimiter;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+#[test]
+fn benchmark_removes_its_tree() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 0, 0);
+    let before = std::fs::read_dir(&test_run.path).unwrap().count();
+
+    let benchmark = benchmark::run(&test_run.path, 5, Arc::new(FsLimiter::default())).unwrap();
+
+    assert_eq!(benchmark.dir_count, 30);
+    assert!(benchmark.size > 0);
+    assert!(benchmark.dirs_per_sec() > 0.0);
+    assert!(benchmark.mib_per_sec() > 0.0);
+
+    assert_eq!(std::fs::read_dir(&test_run.path).unwrap().count(), before);
+}
+
+#[test]
+fn run_with_benchmark() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 2, 0);
+    let before = std::fs::read_dir(&test_run.path).unwrap().count();
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language: LanguageEnum::Target,
+        benchmark: Some(3),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    let exit_code = {
+        let mut wipe = Wipe::new(&mut buff, &params);
+        wipe.run().unwrap();
+        wipe.exit_code()
+    };
+    assert_eq!(exit_code, 0);
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert!(output.contains("Searched 18 folders"));
+    assert!(output.contains("folders/s"));
+
+    // the tree is made in a temporary folder, and nothing is searched or wiped
+    assert!(output.contains(&std::env::temp_dir().display().to_string()));
+    assert_eq!(std::fs::read_dir(&test_run.path).unwrap().count(), before);
+    for path in &test_run.hits {
+        assert!(path.exists());
+    }
+}
+
+#[test]
+fn run_with_benchmark_dir() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 0, 0);
+    let benchmark_dir = test_run.path.join("mount");
+    std::fs::create_dir(&benchmark_dir).unwrap();
+
+    let params = WipeParams {
+        path: std::env::current_dir().unwrap(),
+        language: LanguageEnum::Target,
+        benchmark: Some(2),
+        benchmark_dir: Some(benchmark_dir.clone()),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert!(output.contains(&benchmark_dir.display().to_string()));
+    assert!(output.contains("Searched 12 folders"));
+    assert_eq!(std::fs::read_dir(&benchmark_dir).unwrap().count(), 0);
+}
@@ -0,0 +1,54 @@
+use parameterized::parameterized;
+use std::io;
+use std::path::PathBuf;
+
+use crate::command::LanguageEnum;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+/// Counts how often it is flushed.
+#[derive(Default)]
+struct FlushCounter {
+    written: Vec<u8>,
+    flushes: usize,
+}
+
+impl io::Write for FlushCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[parameterized(buffered = { false, true })]
+fn run_with_buffered(buffered: bool) {
+    let language = LanguageEnum::NodeModules;
+    let test_run = TestRun::new(&language, 20, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        buffered,
+        ..WipeParams::default()
+    };
+
+    let mut writer = FlushCounter::default();
+    Wipe::new(&mut writer, &params).run().unwrap();
+
+    let output = String::from_utf8(writer.written).unwrap();
+    for path in &test_run.hits {
+        assert!(output.contains(&path.display().to_string()));
+    }
+
+    // the header, the summary and the footer, plus every row when not buffered
+    if buffered {
+        assert_eq!(writer.flushes, 3);
+    } else {
+        assert_eq!(writer.flushes, 3 + test_run.hits.len());
+    }
+}
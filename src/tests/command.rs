@@ -1,8 +1,12 @@
 use std::{io, str::FromStr};
 
 use parameterized::parameterized;
+use structopt::StructOpt;
 
-use crate::command::{DirectoryEnum, LanguageEnum};
+use crate::command::{
+    Args, ByteSize, DirectoryEnum, EventsEnum, FormatEnum, LanguageEnum, OutputVersionEnum,
+    PromptDefaultEnum,
+};
 
 #[parameterized(
     language_string = {
@@ -10,6 +14,7 @@ use crate::command::{DirectoryEnum, LanguageEnum};
         "node",
         "target",
         "rust",
+        "coverage",
         "TARGET",
         "target ",
     },
@@ -18,6 +23,7 @@ use crate::command::{DirectoryEnum, LanguageEnum};
         LanguageEnum::Node,
         LanguageEnum::Target,
         LanguageEnum::Rust,
+        LanguageEnum::Coverage,
         LanguageEnum::Target,
         LanguageEnum::Target,
     },
@@ -40,7 +46,7 @@ fn language_string_to_enum_error(language_string: &str) {
     let err = result.err().unwrap();
 
     assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
-    assert_eq!(err.to_string(), "Valid options are: rust | node");
+    assert_eq!(err.to_string(), "Valid options are: rust | node | coverage");
 }
 
 #[parameterized(
@@ -63,12 +69,14 @@ fn language_enum_to_string(language_enum: LanguageEnum, language_string: &str) {
         LanguageEnum::NodeModules,
         LanguageEnum::Rust,
         LanguageEnum::Target,
+        LanguageEnum::Coverage,
     },
     expected_directory_enum = {
         DirectoryEnum::NodeModules,
         DirectoryEnum::NodeModules,
         DirectoryEnum::Target,
         DirectoryEnum::Target,
+        DirectoryEnum::Coverage,
     },
 )]
 fn language_enum_to_directory_enum(
@@ -83,12 +91,134 @@ fn language_enum_to_directory_enum(
     directory_enum = {
         DirectoryEnum::NodeModules,
         DirectoryEnum::Target,
+        DirectoryEnum::Coverage,
     },
     directory_string = {
         "node_modules",
         "target",
+        "coverage",
     },
 )]
 fn directory_enum_to_string(directory_enum: DirectoryEnum, directory_string: &str) {
     assert_eq!(directory_enum.to_string(), directory_string);
 }
+
+#[parameterized(
+    format_string = { "text", "xml", "XML", " xml", "json", "markdown" },
+    format_enum = {
+        FormatEnum::Text,
+        FormatEnum::Xml,
+        FormatEnum::Xml,
+        FormatEnum::Xml,
+        FormatEnum::Json,
+        FormatEnum::Markdown,
+    },
+)]
+fn format_string_to_enum(format_string: &str, format_enum: FormatEnum) {
+    assert_eq!(FormatEnum::from_str(format_string).unwrap(), format_enum);
+}
+
+#[test]
+fn format_string_to_enum_error() {
+    let err = FormatEnum::from_str("yaml").err().unwrap();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "Valid options are: text | xml | json | markdown"
+    );
+}
+
+#[parameterized(
+    prompt_default_string = { "yes", "NO", " no" },
+    prompt_default_enum = { PromptDefaultEnum::Yes, PromptDefaultEnum::No, PromptDefaultEnum::No },
+)]
+fn prompt_default_string_to_enum(
+    prompt_default_string: &str,
+    prompt_default_enum: PromptDefaultEnum,
+) {
+    assert_eq!(
+        PromptDefaultEnum::from_str(prompt_default_string).unwrap(),
+        prompt_default_enum
+    );
+}
+
+#[parameterized(
+    output_version_string = { "1", "2", " 1" },
+    output_version_enum = { OutputVersionEnum::V1, OutputVersionEnum::V2, OutputVersionEnum::V1 },
+)]
+fn output_version_string_to_enum(
+    output_version_string: &str,
+    output_version_enum: OutputVersionEnum,
+) {
+    assert_eq!(
+        OutputVersionEnum::from_str(output_version_string).unwrap(),
+        output_version_enum
+    );
+}
+
+#[parameterized(output_version_string = { "0", "3", "v1" })]
+fn output_version_string_to_enum_error(output_version_string: &str) {
+    let err = OutputVersionEnum::from_str(output_version_string)
+        .err()
+        .unwrap();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(err.to_string(), "Valid options are: 1 | 2");
+}
+
+#[parameterized(events_string = { "ndjson", "NDJSON", " ndjson" })]
+fn events_string_to_enum(events_string: &str) {
+    assert_eq!(
+        EventsEnum::from_str(events_string).unwrap(),
+        EventsEnum::Ndjson
+    );
+}
+
+#[test]
+fn events_string_to_enum_error() {
+    let err = EventsEnum::from_str("json").err().unwrap();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(err.to_string(), "Valid options are: ndjson");
+}
+
+#[parameterized(
+    size_string = { "0", "512", "512b", "1k", "1KB", "1KiB", "1.5MiB", "2 GiB", "1tb" },
+    size = {
+        0,
+        512,
+        512,
+        1024,
+        1024,
+        1024,
+        1024_usize.pow(2) * 3 / 2,
+        2 * 1024_usize.pow(3),
+        1024_usize.pow(4),
+    },
+)]
+fn size_string_to_byte_size(size_string: &str, size: usize) {
+    assert_eq!(ByteSize::from_str(size_string).unwrap(), ByteSize(size));
+}
+
+#[parameterized(size_string = { "", "MiB", "1.5.5MiB", "12 apples", "-1" })]
+fn size_string_to_byte_size_error(size_string: &str) {
+    let err = ByteSize::from_str(size_string).err().unwrap();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn benchmark_without_language() {
+    let args = Args::from_iter_safe(["wipe", "--benchmark"]).unwrap();
+
+    assert!(args.benchmark);
+    assert_eq!(args.language, None);
+
+    // searching still needs one
+    assert!(Args::from_iter_safe(["wipe"]).is_err());
+    assert_eq!(
+        Args::from_iter_safe(["wipe", "node"]).unwrap().language,
+        Some(LanguageEnum::Node)
+    );
+}
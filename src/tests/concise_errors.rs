@@ -0,0 +1,58 @@
+use std::io;
+
+use crate::report::{group_walk_errors, ErrorPhase, ErrorReport};
+
+fn error(path: &str, phase: ErrorPhase, kind: io::ErrorKind) -> ErrorReport {
+    ErrorReport {
+        path: String::from(path),
+        phase,
+        message: io::Error::from(kind).to_string(),
+        kind,
+    }
+}
+
+#[test]
+fn group_walk_errors_by_kind() {
+    let mut errors = (0..42)
+        .map(|i| {
+            error(
+                &format!("/tmp/{i}"),
+                ErrorPhase::Walk,
+                io::ErrorKind::PermissionDenied,
+            )
+        })
+        .collect::<Vec<_>>();
+    errors.insert(
+        3,
+        error("/tmp/gone", ErrorPhase::Walk, io::ErrorKind::NotFound),
+    );
+    errors.push(error(
+        "/tmp/size",
+        ErrorPhase::Size,
+        io::ErrorKind::PermissionDenied,
+    ));
+
+    let groups = group_walk_errors(&errors);
+    let lines = groups.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        lines,
+        vec![
+            "42 directories skipped (permission denied)",
+            "1 directory skipped (entity not found)",
+        ]
+    );
+    assert_eq!(groups[0].errors[0].path, "/tmp/0");
+    assert_eq!(groups[0].errors[41].path, "/tmp/41");
+}
+
+#[test]
+fn group_walk_errors_empty() {
+    let errors = vec![error(
+        "/tmp/delete",
+        ErrorPhase::Delete,
+        io::ErrorKind::Other,
+    )];
+
+    assert!(group_walk_errors(&errors).is_empty());
+}
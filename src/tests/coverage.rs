@@ -0,0 +1,49 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::command::LanguageEnum;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+#[test]
+fn run_with_coverage() {
+    let test_run = TestRun::new(&LanguageEnum::Coverage, 0, 0);
+
+    let hits = [
+        test_run.path.join("web").join("coverage"),
+        test_run.path.join("web").join(".nyc_output"),
+        test_run.path.join("py").join("htmlcov"),
+    ];
+    for path in &hits {
+        fs::create_dir_all(path).unwrap();
+        fs::write(path.join("index.html"), "covered").unwrap();
+    }
+
+    // a file named like a match is not a folder to wipe
+    let miss = test_run.path.join("lib");
+    fs::create_dir_all(&miss).unwrap();
+    fs::write(miss.join("coverage"), "data").unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language: LanguageEnum::Coverage,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    let mut wipe = Wipe::new(&mut buff, &params);
+    wipe.run().unwrap();
+
+    let mut found = wipe
+        .report()
+        .folders
+        .iter()
+        .map(|folder| PathBuf::from(&folder.path))
+        .collect::<Vec<_>>();
+    found.sort();
+
+    let mut expected = hits.to_vec();
+    expected.sort();
+    assert_eq!(found, expected);
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[test]
+fn run_with_deny_file() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let denied = &test_run.hits[0];
+    // a path that goes through `..` still names the same folder
+    let roundabout = denied
+        .parent()
+        .unwrap()
+        .join("..")
+        .join(denied.parent().unwrap().file_name().unwrap())
+        .join("target");
+
+    let deny_file = test_run.path.join("keep.txt");
+    fs::write(
+        &deny_file,
+        format!(
+            "# always keep\n\n{}\n{}\n",
+            roundabout.display(),
+            test_run.path.join("gone").display()
+        ),
+    )
+    .unwrap();
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        deny_file: Some(deny_file),
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+
+    for path in &test_run.hits {
+        let folder = folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap();
+
+        if path == denied {
+            assert_eq!(folder["skipped"], "denied");
+            assert!(path.exists());
+        } else {
+            assert!(folder["skipped"].is_null());
+            assert!(!path.exists());
+        }
+    }
+}
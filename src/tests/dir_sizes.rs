@@ -0,0 +1,250 @@
+use parameterized::parameterized;
+use std::io::Cursor;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::command::DirectoryEnum;
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::dir_helpers::{
+    dir_size, dir_sizes_ordered, find_paths, get_paths_to_delete, sizes_ordered, DepthBand,
+};
+use crate::fs_limiter::FsLimiter;
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::{Wipe, WipeParams};
+
+fn test_run_with_sizes(count: u32) -> TestRun {
+    let test_run = TestRun::new(&LanguageEnum::Target, count, 0);
+
+    // folders of very different sizes finish in a different order than they started
+    for (i, path) in test_run.hits.iter().enumerate() {
+        for j in 0..(i % 4) * 50 {
+            std::fs::write(path.join(format!("file_{j}")), vec![0; i + 1]).unwrap();
+        }
+    }
+
+    test_run
+}
+
+#[test]
+fn dir_sizes_ordered_keeps_order() {
+    let test_run = test_run_with_sizes(20);
+    let limiter = Arc::new(FsLimiter::default());
+
+    let paths = get_paths_to_delete(
+        &test_run.path,
+        &LanguageEnum::Target.into(),
+        &[],
+        DepthBand::default(),
+        &[],
+        &limiter,
+        false,
+    )
+    .unwrap()
+    .into_iter()
+    .map(|p| p.unwrap())
+    .collect::<Vec<_>>();
+
+    let sizes = dir_sizes_ordered(paths.clone(), limiter, 0, false).collect::<Vec<_>>();
+    assert_eq!(sizes.len(), paths.len());
+
+    for (i, (index, dir_info, _, samples)) in sizes.into_iter().enumerate() {
+        let expected = dir_size(&paths[i], &FsLimiter::default()).unwrap();
+        let dir_info = dir_info.unwrap();
+
+        assert_eq!(index, i);
+        assert!(samples.is_empty());
+        assert_eq!(dir_info.file_count, expected.file_count);
+        assert_eq!(dir_info.size, expected.size);
+    }
+}
+
+#[parameterized(catch_panics = { false, true })]
+fn find_paths_with_panic(catch_panics: bool) {
+    let test_run = TestRun::new(&LanguageEnum::Target, 3, 0);
+    let poisoned = test_run.path.join("poisoned");
+    std::fs::create_dir_all(poisoned.join("poison")).unwrap();
+
+    let directory = DirectoryEnum::Target;
+    let is_match = |file_name: &str| {
+        if file_name == "poison" {
+            panic!("malformed entry");
+        }
+
+        directory.names().contains(&file_name)
+    };
+
+    let paths = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        find_paths(
+            test_run.path.clone(),
+            &directory,
+            DepthBand::default(),
+            &[],
+            &FsLimiter::default(),
+            catch_panics,
+            &is_match,
+        )
+        .unwrap()
+    }));
+
+    let Ok(paths) = paths else {
+        assert!(!catch_panics);
+        return;
+    };
+    assert!(catch_panics);
+
+    let (found, errors): (Vec<_>, Vec<_>) = paths.into_iter().partition(|p| p.is_ok());
+
+    // the other subtrees are still searched
+    let mut found = found.into_iter().map(|p| p.unwrap()).collect::<Vec<_>>();
+    let mut hits = test_run
+        .hits
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>();
+    found.sort();
+    hits.sort();
+    assert_eq!(found, hits);
+
+    let errors = errors
+        .into_iter()
+        .map(|p| p.unwrap_err())
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, poisoned.display().to_string());
+    assert_eq!(errors[0].error.to_string(), "panicked: malformed entry");
+}
+
+#[parameterized(catch_panics = { false, true })]
+fn sizes_ordered_with_panic(catch_panics: bool) {
+    let test_run = test_run_with_sizes(8);
+    let paths = test_run
+        .hits
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>();
+    let poisoned = paths[3].clone();
+
+    let sizes = sizes_ordered(paths.clone(), catch_panics, move |path| {
+        if path == poisoned {
+            panic!("malformed entry");
+        }
+
+        (dir_size(path, &FsLimiter::default()), Vec::new())
+    });
+
+    let sizes = std::panic::catch_unwind(AssertUnwindSafe(|| sizes.collect::<Vec<_>>()));
+
+    let Ok(sizes) = sizes else {
+        assert!(!catch_panics);
+        return;
+    };
+    assert!(catch_panics);
+    assert_eq!(sizes.len(), paths.len());
+
+    for (i, (index, dir_info, _, _)) in sizes.into_iter().enumerate() {
+        assert_eq!(index, i);
+
+        if i == 3 {
+            let error = dir_info.unwrap_err();
+            assert_eq!(error.to_string(), "panicked: malformed entry");
+        } else {
+            let expected = dir_size(&paths[i], &FsLimiter::default()).unwrap();
+            assert_eq!(dir_info.unwrap().size, expected.size);
+        }
+    }
+}
+
+#[test]
+fn run_keeps_order_and_totals() {
+    let test_run = test_run_with_sizes(20);
+    let limiter = FsLimiter::default();
+
+    let paths = get_paths_to_delete(
+        &test_run.path,
+        &LanguageEnum::Target.into(),
+        &[],
+        DepthBand::default(),
+        &[],
+        &limiter,
+        false,
+    )
+    .unwrap()
+    .into_iter()
+    .map(|p| p.unwrap())
+    .collect::<Vec<_>>();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language: LanguageEnum::Target,
+        ..WipeParams::default()
+    };
+
+    let mut outputs = Vec::new();
+
+    for _ in 0..3 {
+        let mut buff = Cursor::new(Vec::new());
+        Wipe::new(&mut buff, &params).run().unwrap();
+
+        outputs.push(String::from_utf8(buff.into_inner()).unwrap());
+    }
+
+    // the rows are written in the order the folders were found
+    let rows = outputs[0]
+        .lines()
+        .filter_map(|l| paths.iter().position(|p| l.ends_with(p.as_str())))
+        .collect::<Vec<_>>();
+    assert_eq!(rows, (0..paths.len()).collect::<Vec<_>>());
+
+    assert!(outputs.iter().all(|o| o == &outputs[0]));
+
+    let json = run_json(&WipeParams {
+        format: FormatEnum::Json,
+        ..params
+    });
+
+    let expected = paths
+        .iter()
+        .map(|p| dir_size(p, &limiter).unwrap().size)
+        .sum::<usize>();
+    assert_eq!(json["summary"]["wipe"]["size"], expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn dir_size_counts_symlinks() {
+    use std::os::unix::fs::symlink;
+
+    let test_run = TestRun::new(&LanguageEnum::NodeModules, 1, 0);
+    let node_modules = &test_run.hits[0];
+
+    let package = node_modules.join(".pnpm/foo@1.0.0/node_modules/foo");
+    std::fs::create_dir_all(&package).unwrap();
+    std::fs::write(package.join("index.js"), [0; 100]).unwrap();
+    symlink(&package, node_modules.join("foo")).unwrap();
+    symlink(package.join("index.js"), package.join("main.js")).unwrap();
+    std::fs::create_dir_all(node_modules.join(".bin")).unwrap();
+    symlink("../foo/index.js", node_modules.join(".bin/foo")).unwrap();
+
+    let dir_info = dir_size(node_modules, &FsLimiter::default()).unwrap();
+    assert_eq!(dir_info.symlink_count, 3);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language: LanguageEnum::NodeModules,
+        format: FormatEnum::Json,
+        show_symlinks: true,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folder = json["folders"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["path"] == node_modules.display().to_string())
+        .unwrap();
+
+    assert_eq!(folder["symlink_count"], 3);
+}
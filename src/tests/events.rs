@@ -0,0 +1,66 @@
+use serde_json::Value;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::command::{EventsEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+#[test]
+fn run_with_ndjson_events() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 1);
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        events: Some(EventsEnum::Ndjson),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    let events = output
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    // every folder is found, and deleted right away unless it is ignored
+    let mut expected = Vec::new();
+    for event in events.iter().filter(|e| e["type"] == "found") {
+        expected.push("found");
+        if event["skipped"].is_null() {
+            expected.push("deleted");
+        }
+    }
+    expected.push("summary");
+
+    let types = events
+        .iter()
+        .map(|e| e["type"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(types, expected);
+    assert_eq!(types.iter().filter(|t| **t == "found").count(), 4);
+    assert_eq!(types.iter().filter(|t| **t == "deleted").count(), 3);
+
+    for pair in events.windows(2) {
+        if pair[1]["type"] == "deleted" {
+            assert_eq!(pair[1]["path"], pair[0]["path"]);
+        }
+        assert!(pair[0]["timestamp_ms"].as_u64() <= pair[1]["timestamp_ms"].as_u64());
+    }
+
+    for path in &test_run.hits {
+        assert!(!path.exists());
+    }
+
+    let summary = events.last().unwrap();
+    assert_eq!(summary["wipe"]["file_count"], 3);
+    assert_eq!(summary["ignored"]["file_count"], 1);
+}
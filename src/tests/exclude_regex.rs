@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::command::{FormatEnum, LanguageEnum, PathRegex};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[cfg(unix)]
+#[test]
+fn run_with_exclude_regex() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let excluded = [
+        test_run.path.join("sandbox").join("target"),
+        test_run.path.join("scratch").join("foo").join("target"),
+    ];
+    let kept = test_run.path.join("sandboxes").join("target");
+
+    for target in excluded.iter().chain([&kept]) {
+        std::fs::create_dir_all(target).unwrap();
+        std::fs::File::create(target.join(".rustc_info.json")).unwrap();
+    }
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        exclude_regex: vec![PathRegex::from_str("/(sandbox|scratch)(/|$)").unwrap()],
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+
+    assert_eq!(folders.len(), 1);
+    assert_eq!(folders[0]["path"], kept.display().to_string());
+}
+
+#[test]
+fn invalid_exclude_regex() {
+    let err = PathRegex::from_str("(sandbox").err().unwrap();
+
+    assert!(err.to_string().contains("unclosed group"));
+}
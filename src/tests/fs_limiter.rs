@@ -0,0 +1,75 @@
+use parameterized::parameterized;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use crate::command::LanguageEnum;
+use crate::dir_helpers::dir_size;
+use crate::fs_limiter::FsLimiter;
+use crate::tests::helpers::test_run::TestRun;
+
+#[parameterized(max_inflight = { 1, 2, 5 })]
+fn limiter_bounds_inflight_calls(max_inflight: usize) {
+    let limiter = FsLimiter::new(Some(max_inflight));
+    let inflight = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+
+    std::thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..5 {
+                    limiter.run(|| {
+                        let current = inflight.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(current, Ordering::SeqCst);
+
+                        std::thread::sleep(Duration::from_millis(2));
+
+                        inflight.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+            });
+        }
+    });
+
+    assert!(peak.load(Ordering::SeqCst) <= max_inflight);
+    assert!(limiter.peak() <= max_inflight);
+}
+
+#[test]
+fn limiter_releases_after_panic() {
+    let limiter = Arc::new(FsLimiter::new(Some(1)));
+
+    let panicked = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        limiter.run(|| panic!("malformed entry"));
+    }));
+    assert!(panicked.is_err());
+
+    // a leaked slot would block the next call forever
+    let (sender, receiver) = mpsc::channel();
+    let next = Arc::clone(&limiter);
+    std::thread::spawn(move || sender.send(next.run(|| 1)).unwrap());
+
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(1));
+}
+
+#[test]
+fn limiter_bounds_concurrent_sizing() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 5, 0);
+    let limiter = FsLimiter::new(Some(2));
+
+    let expected = dir_size(&test_run.path, &FsLimiter::default()).unwrap();
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let dir_info = dir_size(&test_run.path, &limiter).unwrap();
+
+                assert_eq!(dir_info.file_count, expected.file_count);
+                assert_eq!(dir_info.size, expected.size);
+            });
+        }
+    });
+
+    assert!(limiter.peak() <= 2);
+}
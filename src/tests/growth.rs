@@ -0,0 +1,59 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[test]
+fn run_with_fastest_growing() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    for path in &test_run.hits {
+        fs::write(path.join("data"), [0; 500]).unwrap();
+    }
+
+    let mut params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    let snapshot = test_run.path.join("snapshot.json");
+    fs::write(&snapshot, run_json(&params).to_string()).unwrap();
+
+    let [slow, fast, shrunk] = &test_run.hits[..] else {
+        unreachable!()
+    };
+    fs::write(slow.join("more"), [0; 100]).unwrap();
+    fs::write(fast.join("more"), [0; 1000]).unwrap();
+    fs::write(shrunk.join("data"), [0; 200]).unwrap();
+
+    params.wipe = true;
+    params.snapshot = Some(snapshot);
+    params.fastest_growing = Some(1);
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+    let folder = |path: &PathBuf| {
+        folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap()
+    };
+
+    assert_eq!(folder(slow)["growth"], 100);
+    assert_eq!(folder(fast)["growth"], 1000);
+    assert_eq!(folder(shrunk)["growth"], -300);
+
+    assert!(folder(fast)["skipped"].is_null());
+    assert!(!fast.exists());
+
+    for path in [slow, shrunk] {
+        assert_eq!(folder(path)["skipped"], "slower_growth");
+        assert!(path.exists());
+    }
+}
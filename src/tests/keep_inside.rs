@@ -0,0 +1,115 @@
+use parameterized::parameterized;
+use serde_json::Value;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::{Wipe, WipeParams};
+
+#[parameterized(wipe = { false, true })]
+fn run_with_keep_inside(wipe: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    for hit in &test_run.hits {
+        std::fs::create_dir_all(hit.join("debug/build")).unwrap();
+        std::fs::create_dir_all(hit.join("debug/deps")).unwrap();
+        std::fs::write(hit.join("debug/build/kept.bin"), vec![0; 1000]).unwrap();
+        std::fs::write(hit.join("debug/deps/wiped.bin"), vec![0; 500]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe,
+        verify: wipe,
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        keep_inside: vec![PathBuf::from("debug/build")],
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    for folder in json["folders"].as_array().unwrap() {
+        let size = folder["size"].as_u64().unwrap();
+        let reclaim_bytes = folder["reclaim_bytes"].as_u64().unwrap();
+
+        assert_eq!(reclaim_bytes, size - 1000);
+    }
+
+    for hit in &test_run.hits {
+        assert!(hit.join("debug/build/kept.bin").exists());
+        assert_eq!(hit.join("debug/deps/wiped.bin").exists(), !wipe);
+        assert_eq!(hit.join(".rustc_info.json").exists(), !wipe);
+    }
+
+    if wipe {
+        assert_eq!(json["summary"]["reclaimed_bytes"], 2 * 500);
+        assert_eq!(json["summary"]["lingering"], 0);
+    }
+}
+
+#[test]
+fn run_with_keep_inside_declined() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let workspace = test_run.path.join("workspace");
+    let targets = ["foo", "bar"]
+        .iter()
+        .map(|name| workspace.join(name).join("target"))
+        .collect::<Vec<_>>();
+
+    for target in &targets {
+        std::fs::create_dir_all(target.join("keep")).unwrap();
+        std::fs::write(target.join(".rustc_info.json"), [0; 100]).unwrap();
+        std::fs::write(target.join("keep/kept.bin"), vec![0; 1000]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        coalesce_siblings: true,
+        keep_inside: vec![PathBuf::from("keep")],
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params)
+        .with_input("n\n".as_bytes())
+        .run()
+        .unwrap();
+
+    let json: Value = serde_json::from_slice(buff.get_ref()).unwrap();
+
+    // the declined folders leave the totals, without taking the kept bytes along
+    assert_eq!(json["summary"]["wipe"]["size"], 0);
+    assert_eq!(json["summary"]["ignored"]["size"], 2 * 1100);
+
+    for target in &targets {
+        assert!(target.join(".rustc_info.json").exists());
+    }
+}
+
+#[test]
+fn run_without_keep_inside() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    for folder in json["folders"].as_array().unwrap() {
+        assert!(folder["reclaim_bytes"].is_null());
+    }
+}
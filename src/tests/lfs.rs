@@ -0,0 +1,63 @@
+use parameterized::parameterized;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[parameterized(exclude_if_under_git_lfs = { false, true })]
+fn run_with_exclude_if_under_git_lfs(exclude_if_under_git_lfs: bool) {
+    let language = LanguageEnum::NodeModules;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let repo = test_run.path.join("repo");
+    let tracked = repo.join("web").join("node_modules");
+    let untracked = repo.join("app").join("node_modules");
+    let nested = repo.join("docs").join("node_modules");
+    for path in [&tracked, &untracked, &nested] {
+        std::fs::create_dir_all(path).unwrap();
+    }
+
+    std::fs::create_dir_all(repo.join(".git")).unwrap();
+    std::fs::write(
+        repo.join(".gitattributes"),
+        "# vendored on purpose\n\
+        web/node_modules/** filter=lfs diff=lfs merge=lfs -text\n\
+        app/node_modules/** -text\n\
+        *.psd filter=lfs diff=lfs merge=lfs -text\n",
+    )
+    .unwrap();
+    std::fs::write(
+        repo.join("docs").join(".gitattributes"),
+        "node_modules/*.wasm filter=lfs diff=lfs merge=lfs -text\n",
+    )
+    .unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        exclude_if_under_git_lfs,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+    let skipped = |path: &PathBuf| {
+        folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap()["skipped"]
+            .clone()
+    };
+
+    if exclude_if_under_git_lfs {
+        assert_eq!(skipped(&tracked), "lfs");
+        assert_eq!(skipped(&nested), "lfs");
+    } else {
+        assert!(skipped(&tracked).is_null());
+        assert!(skipped(&nested).is_null());
+    }
+    assert!(skipped(&untracked).is_null());
+}
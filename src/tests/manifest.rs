@@ -0,0 +1,94 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::command::LanguageEnum;
+use crate::fs_limiter::FsLimiter;
+use crate::manifest::write_manifest;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+#[test]
+fn manifest_lists_files_and_sizes() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 1, 0);
+    let folder = test_run.hits.first().unwrap();
+
+    fs::write(folder.join("a.txt"), [0; 3]).unwrap();
+    fs::create_dir_all(folder.join("deps")).unwrap();
+    fs::write(folder.join("deps").join("b.bin"), [0; 5]).unwrap();
+
+    let manifest_dir = test_run.path.join("manifests");
+    let manifest_path = write_manifest(&manifest_dir, folder, &FsLimiter::default()).unwrap();
+
+    assert!(manifest_path.starts_with(&manifest_dir));
+
+    let manifest = fs::read_to_string(manifest_path).unwrap();
+    let deps_b = PathBuf::from("deps").join("b.bin");
+
+    assert_eq!(
+        manifest,
+        format!(
+            "# {}\n0\t.rustc_info.json\n3\ta.txt\n5\t{}\n",
+            folder.display(),
+            deps_b.display()
+        )
+    );
+}
+
+#[test]
+fn manifest_names_do_not_collide() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 0, 0);
+    let manifest_dir = test_run.path.join("manifests");
+
+    // both paths read `b_c_target` once the separators are replaced
+    let folders = [
+        test_run.path.join("b_c").join("target"),
+        test_run.path.join("b").join("c").join("target"),
+    ];
+
+    let manifest_paths = folders
+        .iter()
+        .map(|folder| {
+            fs::create_dir_all(folder).unwrap();
+            write_manifest(&manifest_dir, folder, &FsLimiter::default()).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    assert_ne!(manifest_paths[0], manifest_paths[1]);
+
+    for (folder, manifest_path) in folders.iter().zip(&manifest_paths) {
+        let manifest = fs::read_to_string(manifest_path).unwrap();
+        assert_eq!(manifest, format!("# {}\n", folder.display()));
+    }
+}
+
+#[test]
+fn run_with_manifest() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+    let manifest_dir = test_run.path.join("manifests");
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        manifest: Some(manifest_dir.clone()),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let manifests = fs::read_dir(&manifest_dir)
+        .unwrap()
+        .map(|e| fs::read_to_string(e.unwrap().path()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(manifests.len(), test_run.hits.len());
+
+    for path in &test_run.hits {
+        assert!(!path.exists());
+
+        let header = format!("# {}\n", path.display());
+        assert!(manifests.iter().any(|m| m.starts_with(&header)));
+    }
+}
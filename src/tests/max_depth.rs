@@ -0,0 +1,147 @@
+use parameterized::parameterized;
+use std::path::PathBuf;
+
+use crate::command::{Args, FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[parameterized(
+    max_depth = { None, Some(0), Some(1), Some(2) },
+    expected = { &[true, true, true], &[true, false, false], &[true, true, false], &[true, true, true] },
+)]
+fn run_with_max_depth(max_depth: Option<usize>, expected: &[bool]) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let targets = [
+        test_run.path.join("target"),
+        test_run.path.join("foo").join("target"),
+        test_run.path.join("foo").join("bar").join("target"),
+    ];
+    for target in &targets {
+        std::fs::create_dir_all(target).unwrap();
+        std::fs::File::create(target.join(".rustc_info.json")).unwrap();
+    }
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        max_depth,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+
+    for (target, expected) in targets.iter().zip(expected) {
+        let found = folders
+            .iter()
+            .any(|f| f["path"] == target.display().to_string());
+
+        assert_eq!(found, *expected, "{}", target.display());
+    }
+}
+
+#[parameterized(
+    min_depth = { None, Some(1), Some(2), Some(3) },
+    max_depth = { None, None, None, Some(1) },
+    expected = { &[true, true, true], &[false, true, true], &[false, false, true], &[false, false, false] },
+)]
+fn run_with_min_depth(min_depth: Option<usize>, max_depth: Option<usize>, expected: &[bool]) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let targets = [
+        test_run.path.join("target"),
+        test_run.path.join("foo").join("target"),
+        test_run.path.join("foo").join("bar").join("target"),
+    ];
+    for target in &targets {
+        std::fs::create_dir_all(target).unwrap();
+        std::fs::File::create(target.join(".rustc_info.json")).unwrap();
+    }
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        min_depth,
+        max_depth,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+
+    for (target, expected) in targets.iter().zip(expected) {
+        let found = folders
+            .iter()
+            .any(|f| f["path"] == target.display().to_string());
+
+        assert_eq!(found, *expected, "{}", target.display());
+    }
+}
+
+#[parameterized(no_recurse = { false, true })]
+fn no_recurse_is_max_depth_1(no_recurse: bool) {
+    let args = Args {
+        no_recurse,
+        ..Default::default()
+    };
+
+    let params = WipeParams::new(&args).unwrap();
+
+    assert_eq!(params.max_depth, no_recurse.then_some(1));
+}
+
+#[parameterized(
+    no_recurse = { false, true },
+    expected = { &[true, true, true, true], &[true, true, false, false] },
+)]
+fn run_with_no_recurse(no_recurse: bool, expected: &[bool]) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let targets = [
+        test_run.path.join("target"),
+        test_run.path.join("foo").join("target"),
+        test_run.path.join("foo").join("bar").join("target"),
+        test_run
+            .path
+            .join("foo")
+            .join("bar")
+            .join("baz")
+            .join("target"),
+    ];
+    for target in &targets {
+        std::fs::create_dir_all(target).unwrap();
+        std::fs::File::create(target.join(".rustc_info.json")).unwrap();
+    }
+
+    let args = Args {
+        no_recurse,
+        ..Default::default()
+    };
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        ..WipeParams::new(&args).unwrap()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+    assert_eq!(folders.len(), expected.iter().filter(|e| **e).count());
+
+    // only the projects directly inside are searched, not the ones nested deeper
+    for (target, expected) in targets.iter().zip(expected) {
+        let found = folders
+            .iter()
+            .any(|f| f["path"] == target.display().to_string());
+
+        assert_eq!(found, *expected, "{}", target.display());
+    }
+}
@@ -1,5 +1,34 @@
+mod ascii;
+mod benchmark;
+mod buffered;
 mod command;
+mod concise_errors;
+mod coverage;
+mod deny_file;
+mod dir_sizes;
+mod events;
+mod exclude_regex;
+mod fs_limiter;
+mod growth;
 mod helpers;
+mod keep_inside;
+mod lfs;
+mod manifest;
+mod max_depth;
+mod monorepo;
+mod output_version;
+mod per_project;
+mod pnpm;
+mod report_json;
+mod report_markdown;
+mod report_xml;
+mod sample;
+mod shards;
+mod times;
+mod verify;
 mod wipe;
+mod wipe_from;
+mod wipe_lock;
 mod wipe_params;
 mod wipe_permissions;
+mod wsl;
@@ -0,0 +1,85 @@
+use parameterized::parameterized;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[parameterized(
+    detect_monorepo_tool = { false, true, true },
+    config = { "turbo.json", "turbo.json", "package.json" },
+)]
+fn run_with_detect_monorepo_tool(detect_monorepo_tool: bool, config: &str) {
+    let language = LanguageEnum::Node;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    std::fs::write(test_run.path.join(config), "{}").unwrap();
+
+    let node_modules = test_run.path.join("apps/web/node_modules");
+    let turbo_caches = [
+        test_run.path.join(".turbo"),
+        test_run.path.join("apps/web/.turbo"),
+    ];
+    for path in turbo_caches.iter().chain([&node_modules]) {
+        std::fs::create_dir_all(path).unwrap();
+    }
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        detect_monorepo_tool,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let found = |path: &PathBuf| {
+        json["folders"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|f| f["path"] == path.display().to_string())
+    };
+
+    assert!(found(&node_modules));
+    for path in &turbo_caches {
+        assert_eq!(found(path), detect_monorepo_tool && config == "turbo.json");
+    }
+}
+
+#[test]
+fn run_with_matched_name() {
+    let language = LanguageEnum::Node;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    std::fs::write(test_run.path.join("turbo.json"), "{}").unwrap();
+
+    let node_modules = test_run.path.join("apps/web/node_modules");
+    let turbo = test_run.path.join("apps/web/.turbo");
+    for path in [&node_modules, &turbo] {
+        std::fs::create_dir_all(path).unwrap();
+    }
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        detect_monorepo_tool: true,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let matched_name = |path: &PathBuf| {
+        json["folders"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap()["matched_name"]
+            .clone()
+    };
+
+    assert_eq!(matched_name(&node_modules), "node_modules");
+    assert_eq!(matched_name(&turbo), ".turbo");
+}
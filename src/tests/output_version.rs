@@ -0,0 +1,117 @@
+use serde_json::Value;
+use std::path::PathBuf;
+
+use parameterized::parameterized;
+
+use crate::command::{ByteSize, FormatEnum, LanguageEnum, OutputVersionEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+fn keys(value: &Value) -> Vec<&str> {
+    let mut keys = value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    keys.sort();
+
+    keys
+}
+
+#[test]
+fn run_with_output_version_1() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 1);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        format: FormatEnum::Json,
+        with_times: true,
+        output_version: OutputVersionEnum::V1,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    assert_eq!(json["format_version"], 1);
+    assert_eq!(
+        keys(&json),
+        vec![
+            "directory",
+            "errors",
+            "folders",
+            "format_version",
+            "path",
+            "summary",
+            "wipe"
+        ]
+    );
+    assert_eq!(
+        keys(&json["summary"]),
+        vec!["after", "ignored", "total", "wipe"]
+    );
+
+    let folders = json["folders"].as_array().unwrap();
+    assert_eq!(folders.len(), 4);
+
+    for folder in folders {
+        assert_eq!(
+            keys(folder),
+            vec!["error", "file_count", "path", "size", "skipped"]
+        );
+    }
+}
+
+// the skip reasons added since the first version are written as one it knows
+#[parameterized(
+    output_version = { OutputVersionEnum::V1, OutputVersionEnum::V2 },
+    skipped = { "ignored", "too_small" },
+)]
+fn run_with_newer_skip_reason(output_version: OutputVersionEnum, skipped: &str) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 1, 0);
+    std::fs::write(test_run.hits[0].join("data"), [0; 100]).unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        min_size: Some(ByteSize(1024)),
+        output_version,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    assert_eq!(json["folders"][0]["skipped"], skipped);
+}
+
+// the latest version is the default
+#[test]
+fn run_with_output_version_2() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        with_times: true,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    assert_eq!(json["format_version"], 2);
+    assert_eq!(json["sort_applied"], "path");
+    assert!(json["summary"]["status"].is_string());
+
+    for folder in json["folders"].as_array().unwrap() {
+        assert!(folder["reclaimable"].is_boolean());
+        assert!(folder["modified"].is_string());
+    }
+}
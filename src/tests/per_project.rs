@@ -0,0 +1,57 @@
+use serde_json::Value;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use crate::command::LanguageEnum;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+fn create_target(path: &Path, size: usize) {
+    fs::create_dir_all(path).unwrap();
+    fs::File::create(path.join(".rustc_info.json")).unwrap();
+    fs::write(path.join("data"), vec![0; size]).unwrap();
+}
+
+#[test]
+fn run_with_per_project_report() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 0, 0);
+
+    let project_a = test_run.path.join("project_a");
+    let project_b = test_run.path.join("project_b");
+    create_target(&project_a.join("target"), 10);
+    create_target(&project_a.join("nested").join("target"), 20);
+    create_target(&project_b.join("target"), 5);
+
+    let reports = test_run.path.join("reports");
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language: LanguageEnum::Target,
+        per_project_report: Some(reports.clone()),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    assert_eq!(fs::read_dir(&reports).unwrap().count(), 2);
+
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(reports.join("project_a.json")).unwrap()).unwrap();
+    assert_eq!(report["project"], project_a.display().to_string());
+    assert_eq!(report["folders"].as_array().unwrap().len(), 2);
+    assert_eq!(report["total"]["file_count"], 4);
+    assert_eq!(report["total"]["size"], 30);
+
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(reports.join("project_b.json")).unwrap()).unwrap();
+    assert_eq!(report["project"], project_b.display().to_string());
+    assert_eq!(report["folders"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        report["folders"][0]["path"],
+        project_b.join("target").display().to_string()
+    );
+    assert_eq!(report["total"]["file_count"], 2);
+    assert_eq!(report["total"]["size"], 5);
+}
@@ -0,0 +1,137 @@
+#[cfg(unix)]
+mod pnpm_tests {
+    use parameterized::parameterized;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use crate::command::{FormatEnum, LanguageEnum, PathRegex};
+    use crate::dir_helpers::{dir_size, folder_size};
+    use crate::fs_limiter::FsLimiter;
+    use crate::pnpm::{is_pnpm_layout, store_dir};
+    use crate::tests::helpers::test_run::TestRun;
+    use crate::tests::report_json::run_json;
+    use crate::wipe::WipeParams;
+
+    /// A project with two packages that share the same file through hard links, symlinked into
+    /// `node_modules` the way pnpm does it.
+    fn pnpm_project(test_run: &TestRun) -> (PathBuf, PathBuf) {
+        let node_modules = test_run.path.join("project").join("node_modules");
+        let store = test_run.path.join("store").join("v3");
+        let files = store.join("files").join("00");
+
+        let foo = node_modules.join(".pnpm/foo@1.0.0/node_modules/foo");
+        let bar = node_modules.join(".pnpm/bar@1.0.0/node_modules/bar");
+        fs::create_dir_all(&foo).unwrap();
+        fs::create_dir_all(&bar).unwrap();
+        fs::create_dir_all(&files).unwrap();
+
+        fs::write(files.join("index.js"), [0; 100]).unwrap();
+        fs::hard_link(files.join("index.js"), foo.join("index.js")).unwrap();
+        fs::hard_link(files.join("index.js"), bar.join("index.js")).unwrap();
+
+        symlink(&foo, node_modules.join("foo")).unwrap();
+        symlink(&bar, node_modules.join("bar")).unwrap();
+
+        fs::write(
+            node_modules.join(".modules.yaml"),
+            format!("layoutVersion: 5\nstoreDir: '{}'\n", store.display()),
+        )
+        .unwrap();
+
+        (node_modules, store)
+    }
+
+    #[test]
+    fn pnpm_size_is_not_double_counted() {
+        let test_run = TestRun::new(&LanguageEnum::NodeModules, 0, 0);
+        let (node_modules, store) = pnpm_project(&test_run);
+        let limiter = FsLimiter::default();
+
+        assert!(is_pnpm_layout(&node_modules));
+        assert_eq!(store_dir(&node_modules), Some(store));
+
+        let modules_yaml = fs::metadata(node_modules.join(".modules.yaml"))
+            .unwrap()
+            .len() as usize;
+
+        let naive = dir_size(&node_modules, &limiter).unwrap();
+        assert!(naive.size > 100 + modules_yaml);
+
+        let dir_info = folder_size(&node_modules, &limiter).unwrap();
+        assert_eq!(dir_info.file_count, 2);
+        assert_eq!(dir_info.size, 100 + modules_yaml);
+    }
+
+    #[test]
+    fn run_with_include_pnpm_store() {
+        let test_run = TestRun::new(&LanguageEnum::NodeModules, 0, 0);
+        let (node_modules, store) = pnpm_project(&test_run);
+        let node_modules_size = folder_size(&node_modules, &FsLimiter::default())
+            .unwrap()
+            .size;
+
+        let params = WipeParams {
+            wipe: true,
+            // the store is next to the project, outside of the searched folder
+            path: test_run.path.join("project"),
+            language: LanguageEnum::NodeModules,
+            format: FormatEnum::Json,
+            include_pnpm_store: true,
+            ..WipeParams::default()
+        };
+
+        let json = run_json(&params);
+
+        let folders = json["folders"].as_array().unwrap();
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[0]["path"], node_modules.display().to_string());
+        assert_eq!(folders[1]["path"], store.display().to_string());
+
+        assert!(!node_modules.exists());
+        assert!(!store.exists());
+
+        // the store is outside of the searched folder, so it has totals of its own
+        let summary = &json["summary"];
+        assert_eq!(summary["wipe"]["size"], node_modules_size);
+        assert_eq!(summary["found_bytes"], node_modules_size);
+        assert_eq!(summary["reclaimed_bytes"], node_modules_size);
+        assert_eq!(summary["pnpm_store"]["file_count"], 1);
+        assert_eq!(summary["pnpm_store"]["size"], 100);
+    }
+
+    #[parameterized(reason = { "not_a_store", "excluded" })]
+    fn run_with_unmatched_pnpm_store(reason: &str) {
+        let test_run = TestRun::new(&LanguageEnum::NodeModules, 0, 0);
+        let (node_modules, store) = pnpm_project(&test_run);
+
+        let exclude_regex = match reason {
+            "not_a_store" => {
+                fs::remove_dir_all(store.join("files")).unwrap();
+                Vec::new()
+            }
+            _ => vec![PathRegex::from_str("/store/").unwrap()],
+        };
+
+        let params = WipeParams {
+            wipe: true,
+            path: test_run.path.join("project"),
+            language: LanguageEnum::NodeModules,
+            format: FormatEnum::Json,
+            include_pnpm_store: true,
+            exclude_regex,
+            ..WipeParams::default()
+        };
+
+        let json = run_json(&params);
+
+        let folders = json["folders"].as_array().unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0]["path"], node_modules.display().to_string());
+        assert_eq!(json["summary"]["pnpm_store"], serde_json::Value::Null);
+
+        assert!(!node_modules.exists());
+        assert!(store.exists());
+    }
+}
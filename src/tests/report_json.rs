@@ -0,0 +1,590 @@
+use parameterized::parameterized;
+use serde_json::Value;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use yansi::Paint;
+
+use crate::command::{ByteSize, EventsEnum, FormatEnum, LanguageEnum};
+use crate::report::path_components;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Hooks, Wipe, WipeParams, SPACING_FILES};
+
+pub fn run_json(params: &WipeParams) -> Value {
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    serde_json::from_str(output).unwrap()
+}
+
+#[parameterized(
+    language = {
+        LanguageEnum::NodeModules, LanguageEnum::NodeModules,
+        LanguageEnum::Target, LanguageEnum::Target,
+    },
+    wipe = { false, true, false, true },
+)]
+fn run_with_hits(language: LanguageEnum, wipe: bool) {
+    let test_run = TestRun::new(&language, 3, 1);
+
+    let params = WipeParams {
+        wipe,
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    assert_eq!(json["path"], test_run.path.display().to_string());
+    assert_eq!(json["wipe"], wipe);
+
+    let folders = json["folders"].as_array().unwrap();
+    assert_eq!(folders.len(), test_run.hits.len() + test_run.ignores.len());
+
+    for path in &test_run.hits {
+        let folder = folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap();
+
+        assert_eq!(folder["skipped"], Value::Null);
+        assert_eq!(folder["error"], Value::Null);
+        assert_eq!(path.exists(), !wipe);
+    }
+
+    for path in &test_run.ignores {
+        let folder = folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap();
+
+        assert_eq!(folder["skipped"], "ignored");
+        assert!(path.exists());
+    }
+
+    assert_eq!(json["errors"], Value::Array(Vec::new()));
+}
+
+#[test]
+fn run_with_min_size() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    let small = &test_run.hits[0];
+    let large = &test_run.hits[1];
+    std::fs::write(small.join("data"), [0; 100]).unwrap();
+    std::fs::write(large.join("data"), [0; 2000]).unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        min_size: Some(ByteSize(1024)),
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+    let folder = |path: &PathBuf| {
+        folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap()
+    };
+
+    assert_eq!(folder(small)["reclaimable"], false);
+    assert_eq!(folder(small)["skip_reason"], "too_small");
+    assert_eq!(folder(large)["reclaimable"], true);
+    assert_eq!(folder(large)["skip_reason"], Value::Null);
+
+    assert_eq!(json["summary"]["wipe"]["size"], 2000);
+}
+
+#[test]
+fn run_with_failed_deletion() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    for path in &test_run.hits {
+        std::fs::write(path.join("data"), [0; 100]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    // the folder vanishes right before its removal, which then fails
+    let failing = test_run.hits[0].clone();
+    let hooks = Hooks {
+        before_remove: Some(Box::new(|path: &Path| {
+            if path == failing {
+                std::fs::remove_dir_all(path).unwrap();
+            }
+        })),
+        ..Hooks::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    // the process exits with the same code
+    let process_exit_code = {
+        let mut wipe = Wipe::new(&mut buff, &params).with_hooks(hooks);
+        wipe.run().unwrap();
+        wipe.exit_code()
+    };
+    let json: Value = serde_json::from_slice(buff.get_ref()).unwrap();
+
+    let summary = &json["summary"];
+    assert_eq!(summary["found_bytes"], 300);
+    assert_eq!(summary["reclaimed_bytes"], 200);
+    assert_eq!(summary["failed_count"], 1);
+    assert_eq!(summary["status"], "partial_failure");
+    assert_eq!(summary["exit_code"], process_exit_code);
+    assert_eq!(process_exit_code, 0);
+}
+
+#[parameterized(hits = { 0, 3 }, status = { "empty", "found" })]
+fn run_with_exit_code(hits: u32, status: &str) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, hits, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    // the process exits with the same code
+    let exit_code = {
+        let mut wipe = Wipe::new(&mut buff, &params);
+        wipe.run().unwrap();
+        wipe.exit_code()
+    };
+
+    let json: Value = serde_json::from_slice(buff.get_ref()).unwrap();
+    assert_eq!(json["summary"]["status"], status);
+    assert_eq!(json["summary"]["exit_code"], exit_code);
+    assert_eq!(exit_code, 0);
+}
+
+#[cfg(unix)]
+#[parameterized(with_volume = { false, true })]
+fn run_with_volume(with_volume: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        with_volume,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let volumes = json["folders"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|folder| folder["volume"].clone())
+        .collect::<Vec<_>>();
+
+    if !with_volume {
+        assert!(volumes.iter().all(Value::is_null));
+        return;
+    }
+
+    // all the folders are on the same device as the test folder
+    let volume = volumes[0].as_str().unwrap();
+    assert!(std::fs::canonicalize(&test_run.path)
+        .unwrap()
+        .starts_with(volume));
+    assert!(volumes.iter().all(|v| v == volume));
+}
+
+#[parameterized(
+    format = { FormatEnum::Text, FormatEnum::Json, FormatEnum::Xml, FormatEnum::Text },
+    events = { None, None, None, Some(EventsEnum::Ndjson) },
+    structured = { false, true, true, true },
+)]
+fn structured_formats(format: FormatEnum, events: Option<EventsEnum>, structured: bool) {
+    let params = WipeParams {
+        format,
+        events,
+        ..WipeParams::default()
+    };
+
+    assert_eq!(params.structured(), structured);
+}
+
+#[parameterized(events = { None, Some(EventsEnum::Ndjson) })]
+fn run_without_colors(events: Option<EventsEnum>) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 1);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        format: FormatEnum::Json,
+        events,
+        progress: true,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert!(!output.is_empty());
+    assert!(!output.contains("\x1b["));
+}
+
+#[test]
+fn run_with_progress() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        progress: true,
+        ..WipeParams::default()
+    };
+
+    // the progress goes to stderr, so stdout still parses
+    let json = run_json(&params);
+
+    assert_eq!(
+        json["folders"].as_array().unwrap().len(),
+        test_run.hits.len()
+    );
+}
+
+#[test]
+fn run_twice_with_stable_order() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 10, 2);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    let run = || {
+        let mut buff = Cursor::new(Vec::new());
+        Wipe::new(&mut buff, &params).run().unwrap();
+        buff.into_inner()
+    };
+
+    let first = run();
+    assert_eq!(first, run());
+
+    let json: Value = serde_json::from_slice(&first).unwrap();
+    assert_eq!(json["sort_applied"], "path");
+
+    let paths = json["folders"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|folder| folder["path"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+}
+
+#[parameterized(timings_per_folder = { false, true })]
+fn run_with_timings_per_folder(timings_per_folder: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        timings_per_folder,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    let folders = json["folders"].as_array().unwrap();
+    assert_eq!(folders.len(), test_run.hits.len());
+
+    for folder in folders {
+        let folder = folder.as_object().unwrap();
+
+        if timings_per_folder {
+            // well below a minute for an almost empty folder
+            let size_duration_ms = folder["size_duration_ms"].as_u64().unwrap();
+            assert!(size_duration_ms < 60_000);
+        } else {
+            assert!(!folder.contains_key("size_duration_ms"));
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn path_components_of_fixture() {
+    assert_eq!(
+        path_components("/home/me/proj/target"),
+        vec!["home", "me", "proj", "target"]
+    );
+}
+
+#[parameterized(path_components = { false, true })]
+fn run_with_path_components(path_components: bool) {
+    let language = LanguageEnum::NodeModules;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        path_components,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    for folder in json["folders"].as_array().unwrap() {
+        let folder = folder.as_object().unwrap();
+
+        if path_components {
+            let path = PathBuf::from(folder["path"].as_str().unwrap());
+            let components = folder["path_components"].as_array().unwrap();
+
+            assert_eq!(components.last().unwrap(), "node_modules");
+            assert_eq!(
+                components
+                    .iter()
+                    .map(|c| c.as_str().unwrap())
+                    .collect::<PathBuf>(),
+                path.strip_prefix(path.ancestors().last().unwrap()).unwrap()
+            );
+        } else {
+            assert!(!folder.contains_key("path_components"));
+        }
+    }
+}
+
+#[test]
+fn run_with_summary_json() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 1);
+
+    for path in test_run.hits.iter().chain(&test_run.ignores) {
+        std::fs::write(path.join("data"), [0; 100]).unwrap();
+    }
+
+    let summary_json = test_run.path.join("summary.json");
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        summary_json: Some(summary_json.clone()),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    // stdout is the regular table
+    let expected = format!("{:>files$}", Paint::cyan("Files #"), files = SPACING_FILES);
+    assert!(output.contains(&expected));
+    assert!(serde_json::from_str::<Value>(output).is_err());
+
+    let summary: Value =
+        serde_json::from_str(&std::fs::read_to_string(summary_json).unwrap()).unwrap();
+
+    assert_eq!(summary["wipe"]["file_count"], 6);
+    assert_eq!(summary["wipe"]["size"], 300);
+    assert_eq!(summary["ignored"]["file_count"], 2);
+    assert_eq!(summary["ignored"]["size"], 100);
+    assert_eq!(summary["total"]["size"], 400);
+    assert_eq!(summary["after"]["size"], 100);
+}
+
+#[parameterized(with_shallow = { false, true })]
+fn run_with_shallow(with_shallow: bool) {
+    let language = LanguageEnum::NodeModules;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    for path in &test_run.hits {
+        std::fs::create_dir_all(path.join("pkg/lib")).unwrap();
+        std::fs::write(path.join("index.js"), [0; 100]).unwrap();
+        std::fs::write(path.join("pkg/package.json"), [0; 1000]).unwrap();
+        std::fs::write(path.join("pkg/lib/index.js"), [0; 10000]).unwrap();
+    }
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        with_shallow,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    for path in &test_run.hits {
+        let folder = json["folders"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap()
+            .as_object()
+            .unwrap();
+
+        assert_eq!(folder["size"], 11100);
+
+        if with_shallow {
+            assert_eq!(folder["shallow_size"], 100);
+        } else {
+            assert!(!folder.contains_key("shallow_size"));
+        }
+    }
+}
+
+#[test]
+fn run_with_rebuild_cost() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    let project = test_run.hits[0].parent().unwrap();
+    let packages = (0..3)
+        .map(|i| format!("[[package]]\nname = \"dep_{i}\"\nversion = \"1.0.0\"\n"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(
+        project.join("Cargo.lock"),
+        format!("# This file is automatically @generated by Cargo.\nversion = 3\n\n{packages}"),
+    )
+    .unwrap();
+
+    let mut params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        rebuild_cost: true,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+
+    for folder in json["folders"].as_array().unwrap() {
+        let folder = folder.as_object().unwrap();
+
+        if folder["path"] == test_run.hits[0].display().to_string() {
+            assert_eq!(folder["rebuild_count"], 3);
+        } else {
+            assert!(!folder.contains_key("rebuild_count"));
+        }
+    }
+
+    params.format = FormatEnum::Text;
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    assert_eq!(output.matches("dependencies will recompile").count(), 1);
+    assert!(output.contains("≈ 3 dependencies will recompile"));
+}
+
+#[test]
+fn run_with_git_repos() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    let repo = test_run.hits[0].parent().unwrap();
+    std::fs::create_dir_all(repo.join(".git")).unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+
+    let inside = folders
+        .iter()
+        .find(|f| f["path"] == test_run.hits[0].display().to_string())
+        .unwrap();
+    assert_eq!(inside["in_git_repo"], true);
+    assert_eq!(inside["git_repo_root"], repo.display().to_string());
+
+    let outside = folders
+        .iter()
+        .find(|f| f["path"] == test_run.hits[1].display().to_string())
+        .unwrap()
+        .as_object()
+        .unwrap();
+    assert_eq!(outside["in_git_repo"], false);
+    assert!(!outside.contains_key("git_repo_root"));
+}
+
+#[parameterized(size_precision = { None, Some(0), Some(1), Some(3) })]
+fn run_with_size_precision(size_precision: Option<usize>) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 1, 0);
+    std::fs::write(test_run.hits[0].join("data"), vec![0; 1_597_000]).unwrap();
+
+    let mut params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        size_precision,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folder = json["folders"][0].as_object().unwrap();
+
+    let expected = match size_precision {
+        None => "1.52 MiB",
+        Some(0) => "2 MiB",
+        Some(1) => "1.5 MiB",
+        _ => "1.523 MiB",
+    };
+
+    if size_precision.is_some() {
+        assert_eq!(folder["size_human"], expected);
+    } else {
+        assert!(!folder.contains_key("size_human"));
+    }
+
+    params.format = FormatEnum::Text;
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    assert!(output.contains(&format!(" {expected}")));
+}
@@ -0,0 +1,86 @@
+use parameterized::parameterized;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+#[cfg(unix)]
+#[test]
+fn run_markdown() {
+    let language = LanguageEnum::NodeModules;
+    let test_run = TestRun::new(&language, 2, 1);
+
+    let piped = test_run.path.join("foo|bar").join("node_modules");
+    std::fs::create_dir_all(&piped).unwrap();
+    std::fs::write(piped.join("index.js"), [0; 2048]).unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        format: FormatEnum::Markdown,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert!(!output.contains('\x1b'));
+
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "| Files | Size | Path |");
+    assert_eq!(lines[1], "| ----: | ---: | :--- |");
+
+    let rows = &lines[2..6];
+    for row in rows {
+        assert!(row.starts_with("| ") && row.ends_with(" |"));
+    }
+
+    let escaped = piped.display().to_string().replace('|', "\\|");
+    assert!(rows.contains(&format!("| 1 | 2.00 KiB | {escaped} |").as_str()));
+
+    let ignored = format!("{} (ignored) |", test_run.ignores[0].display());
+    assert!(rows.iter().any(|row| row.ends_with(&ignored)));
+
+    assert_eq!(lines[6], "");
+    assert!(lines[7].starts_with("**Can wipe:** 1 files, 2.00 KiB of the 2.00 KiB in "));
+    assert_eq!(lines.len(), 8);
+}
+
+#[parameterized(
+    size_precision = { None, Some(0), Some(3) },
+    expected = { "13.67 KiB", "14 KiB", "13.672 KiB" },
+)]
+fn run_markdown_with_size_precision(size_precision: Option<usize>, expected: &str) {
+    let language = LanguageEnum::NodeModules;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let node_modules = test_run.path.join("foo").join("node_modules");
+    std::fs::create_dir_all(&node_modules).unwrap();
+    std::fs::write(node_modules.join("index.js"), [0; 14_000]).unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Markdown,
+        size_precision,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    let row = format!("| 1 | {expected} | {} |", node_modules.display());
+    assert!(output.lines().any(|line| line == row));
+
+    let total = format!("**Can wipe:** 1 files, {expected} of the {expected} in ");
+    assert!(output.contains(&total));
+}
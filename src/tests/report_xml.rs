@@ -0,0 +1,108 @@
+use quick_xml::events::Event;
+use quick_xml::{Reader, XmlVersion};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+fn parse(output: &str) -> Vec<(String, HashMap<String, String>)> {
+    let mut reader = Reader::from_str(output);
+    let mut elements = Vec::new();
+
+    loop {
+        match reader.read_event().unwrap() {
+            Event::Start(e) | Event::Empty(e) => {
+                let name = e.name().0.to_string();
+                let attributes = e
+                    .attributes()
+                    .map(|a| {
+                        let a = a.unwrap();
+                        (
+                            a.key.0.to_string(),
+                            a.normalized_value(XmlVersion::Explicit1_0)
+                                .unwrap()
+                                .to_string(),
+                        )
+                    })
+                    .collect();
+
+                elements.push((name, attributes));
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+
+    elements
+}
+
+#[test]
+fn run_xml() {
+    let language = LanguageEnum::Target;
+    let mut test_run = TestRun::new(&language, 2, 0);
+
+    // a path that needs escaping
+    let special = test_run.path.join(r#"a&b<"c">"#).join("target");
+    std::fs::create_dir_all(&special).unwrap();
+    std::fs::File::create(special.join(".rustc_info.json")).unwrap();
+    std::fs::write(special.join("data"), [0; 10]).unwrap();
+    test_run.hits.push(special.clone());
+
+    let params = WipeParams {
+        wipe: false,
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Xml,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert!(output.starts_with("<?xml"));
+    assert!(!output.contains('\u{1b}'));
+    assert!(output.contains("a&amp;b&lt;&quot;c&quot;&gt;"));
+
+    let elements = parse(output);
+
+    let (name, root) = &elements[0];
+    assert_eq!(name, "wipe");
+    assert_eq!(root["path"], test_run.path.display().to_string());
+    assert_eq!(root["directory"], "target");
+    assert_eq!(root["wipe"], "false");
+
+    let folders = elements
+        .iter()
+        .filter(|(name, _)| name == "folder")
+        .map(|(_, attributes)| (attributes["path"].clone(), attributes))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(folders.len(), test_run.hits.len());
+
+    for path in &test_run.hits {
+        let folder = folders[&path.display().to_string()];
+
+        if path == &special {
+            assert_eq!(folder["files"], "2");
+            assert_eq!(folder["bytes"], "10");
+        } else {
+            assert_eq!(folder["files"], "1");
+            assert_eq!(folder["bytes"], "0");
+        }
+        assert!(!folder.contains_key("skipped"));
+        assert!(path.exists());
+    }
+
+    let (_, summary_wipe) = elements
+        .iter()
+        .skip_while(|(name, _)| name != "summary")
+        .find(|(name, _)| name == "wipe")
+        .unwrap();
+    assert_eq!(summary_wipe["files"], "4");
+    assert_eq!(summary_wipe["bytes"], "10");
+}
@@ -0,0 +1,51 @@
+use parameterized::parameterized;
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[parameterized(sample = { None, Some(0), Some(2), Some(10) })]
+fn run_with_sample(sample: Option<usize>) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    for hit in &test_run.hits {
+        std::fs::create_dir_all(hit.join("debug/deps")).unwrap();
+        for i in 0..4 {
+            std::fs::write(hit.join("debug/deps").join(format!("lib{i}.rlib")), "x").unwrap();
+        }
+    }
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        sample,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+    assert_eq!(folders.len(), 3);
+
+    for folder in folders {
+        let Some(sample) = sample else {
+            assert!(folder["samples"].is_null());
+            continue;
+        };
+
+        let path = PathBuf::from(folder["path"].as_str().unwrap());
+        let samples = folder["samples"].as_array().unwrap();
+
+        // every hit holds 5 files
+        assert_eq!(samples.len(), sample.min(5));
+
+        for file in samples {
+            let file = file.as_str().unwrap();
+            assert!(PathBuf::from(file).is_relative());
+            assert!(path.join(file).is_file());
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde_json::Value;
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::command::LanguageEnum;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+
+#[test]
+fn run_with_shard_output() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 5, 0);
+    let shards = test_run.path.join("shards");
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        shard_output: Some(shards.clone()),
+        shard_size: 2,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(shards.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["folder_count"], 5);
+    assert_eq!(
+        manifest["shards"],
+        serde_json::json!(["part-0001.ndjson", "part-0002.ndjson", "part-0003.ndjson"])
+    );
+    assert_eq!(manifest["summary"]["wipe"]["file_count"], 5);
+
+    let mut paths = Vec::new();
+    for shard in manifest["shards"].as_array().unwrap() {
+        let shard = fs::read_to_string(shards.join(shard.as_str().unwrap())).unwrap();
+        let lines = shard.lines().collect::<Vec<_>>();
+        assert!(lines.len() <= 2);
+
+        for line in lines {
+            let folder: Value = serde_json::from_str(line).unwrap();
+            paths.push(PathBuf::from(folder["path"].as_str().unwrap()));
+        }
+    }
+
+    paths.sort();
+    let mut hits = test_run.hits.clone();
+    hits.sort();
+    assert_eq!(paths, hits);
+}
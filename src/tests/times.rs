@@ -0,0 +1,57 @@
+use parameterized::parameterized;
+use regex::Regex;
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::times::iso8601;
+use crate::wipe::WipeParams;
+
+#[parameterized(
+    secs = { 0, 951_782_400, 1_710_000_000, 4_107_542_399 },
+    expected = {
+        "1970-01-01T00:00:00Z",
+        "2000-02-29T00:00:00Z",
+        "2024-03-09T16:00:00Z",
+        "2100-02-28T23:59:59Z",
+    },
+)]
+fn formats_iso8601(secs: u64, expected: &str) {
+    let time = UNIX_EPOCH + Duration::from_secs(secs);
+
+    assert_eq!(iso8601(time).unwrap(), expected);
+}
+
+#[parameterized(with_times = { false, true })]
+fn run_with_times(with_times: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        with_times,
+        ..WipeParams::default()
+    };
+
+    let json = run_json(&params);
+    let iso8601 = Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}Z$").unwrap();
+
+    for folder in json["folders"].as_array().unwrap() {
+        if !with_times {
+            assert!(folder["created"].is_null());
+            assert!(folder["modified"].is_null());
+            continue;
+        }
+
+        assert!(iso8601.is_match(folder["modified"].as_str().unwrap()));
+
+        // not every filesystem records when a folder was created
+        if let Some(created) = folder["created"].as_str() {
+            assert!(iso8601.is_match(created));
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use parameterized::parameterized;
+use serde_json::Value;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Hooks, Wipe, WipeParams};
+
+#[parameterized(verify = { false, true })]
+fn run_with_verify(verify: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+    let recreated = test_run.hits[0].clone();
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        format: FormatEnum::Json,
+        verify,
+        ..WipeParams::default()
+    };
+
+    // something running at the same time creates the folder again
+    let hooks = Hooks {
+        after_delete: Some(Box::new(|path: &Path| {
+            if path == recreated {
+                std::fs::create_dir(path).unwrap();
+            }
+        })),
+        ..Hooks::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    let exit_code = {
+        let mut wipe = Wipe::new(&mut buff, &params).with_hooks(hooks);
+        wipe.run().unwrap();
+        wipe.exit_code()
+    };
+
+    let json: Value = serde_json::from_slice(buff.get_ref()).unwrap();
+    let folders = json["folders"].as_array().unwrap();
+    let folder = |path: &PathBuf| {
+        folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap()
+    };
+
+    if !verify {
+        assert!(folder(&recreated)["verified"].is_null());
+        assert!(json["summary"]["lingering"].is_null());
+        assert_eq!(exit_code, 0);
+        return;
+    }
+
+    assert_eq!(folder(&recreated)["verified"], false);
+    for path in &test_run.hits[1..] {
+        assert_eq!(folder(path)["verified"], true);
+    }
+
+    assert_eq!(json["summary"]["lingering"], 1);
+    assert_eq!(json["summary"]["status"], "lingering");
+    assert_ne!(exit_code, 0);
+}
+
+#[test]
+fn run_with_verify_text() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 2, 0);
+    let recreated = test_run.hits[0].clone();
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        verify: true,
+        ..WipeParams::default()
+    };
+
+    let hooks = Hooks {
+        after_delete: Some(Box::new(|path: &Path| {
+            if path == recreated {
+                std::fs::create_dir(path).unwrap();
+            }
+        })),
+        ..Hooks::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params)
+        .with_hooks(hooks)
+        .run()
+        .unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert!(output.contains("1 of the wiped folders are still present:"));
+    assert_eq!(output.matches(&recreated.display().to_string()).count(), 2);
+}
@@ -1,11 +1,11 @@
 use parameterized::parameterized;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{io::Cursor, println};
 use yansi::Paint;
 
-use crate::command::LanguageEnum;
+use crate::command::{Args, ByteSize, LanguageEnum, PromptDefaultEnum};
 use crate::tests::helpers::test_run::TestRun;
-use crate::wipe::{Wipe, WipeParams, SPACING_FILES, SPACING_SIZE};
+use crate::wipe::{Hooks, Wipe, WipeParams, SPACING_FILES, SPACING_SIZE};
 
 #[parameterized(
     language = {
@@ -22,6 +22,7 @@ fn run_with_hits(language: LanguageEnum, wipe: bool) {
         path: PathBuf::from(&test_run),
         language: language.clone(),
         ignores: Vec::new(),
+        ..WipeParams::default()
     };
 
     let mut buff = Cursor::new(Vec::new());
@@ -80,7 +81,7 @@ fn run_with_hits(language: LanguageEnum, wipe: bool) {
     } else {
         let expected = format!(
             "Run {} to wipe all folders found. {}",
-            Paint::red(format!("cargo wipe {} -w", params.language)),
+            Paint::red(format!("cargo wipe {} -w --force", params.language)),
             Paint::red("USE WITH CAUTION!")
         );
         assert!(output.contains(&expected));
@@ -102,6 +103,7 @@ fn run_no_hits(language: LanguageEnum, wipe: bool) {
         path: PathBuf::from(&test_run),
         language,
         ignores: Vec::new(),
+        ..WipeParams::default()
     };
 
     let mut buff = Cursor::new(Vec::new());
@@ -156,6 +158,7 @@ fn run_with_ignores(language: LanguageEnum, wipe: bool) {
         path: PathBuf::from(&test_run),
         language,
         ignores: test_run.ignores.clone(),
+        ..WipeParams::default()
     };
 
     let mut buff = Cursor::new(Vec::new());
@@ -208,3 +211,573 @@ fn run_with_ignores(language: LanguageEnum, wipe: bool) {
     let expected = format!("{}", Paint::yellow("Ignored"));
     assert!(output.contains(&expected));
 }
+
+#[parameterized(
+    language = {
+        LanguageEnum::NodeModules, LanguageEnum::NodeModules,
+        LanguageEnum::Target, LanguageEnum::Target,
+    },
+    wipe = { false, true, false, true },
+)]
+fn run_with_expect_at_least(language: LanguageEnum, wipe: bool) {
+    let test_run = TestRun::new(&language, 3, 0);
+
+    for path in &test_run.hits {
+        std::fs::write(path.join("data"), [0; 100]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe,
+        path: PathBuf::from(&test_run),
+        language: language.clone(),
+        expect_at_least: Some(ByteSize(301)),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    let err = Wipe::new(&mut buff, &params).run().err().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert_eq!(
+        err.to_string(),
+        "Only 300 bytes can be wiped, but at least 301 bytes was expected"
+    );
+
+    // nothing should be listed or wiped
+    for path in &test_run.hits {
+        let expected = String::from(path.to_str().unwrap());
+        assert!(!output.contains(&expected));
+        assert!(path.exists());
+    }
+
+    // enough to wipe
+    let params = WipeParams {
+        expect_at_least: Some(ByteSize(300)),
+        ..params
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    for path in &test_run.hits {
+        assert_eq!(path.exists(), !wipe);
+    }
+}
+
+#[parameterized(
+    wipe = { true, true, false },
+    min_reclaim_ratio = { 0.2, 0.15, 0.2 },
+    allowed = { false, true, true },
+)]
+fn run_with_min_reclaim_ratio(wipe: bool, min_reclaim_ratio: f64, allowed: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    for path in &test_run.hits {
+        std::fs::write(path.join("data"), [0; 100]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe,
+        path: PathBuf::from(&test_run),
+        language,
+        min_reclaim_ratio: Some(min_reclaim_ratio),
+        ..WipeParams::default()
+    };
+
+    // 300 bytes can be wiped, which is 15% of the free space
+    let hooks = Hooks {
+        free_space: Some(Box::new(|_: &Path| Ok(2000))),
+        ..Hooks::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    let result = Wipe::new(&mut buff, &params).with_hooks(hooks).run();
+
+    if allowed {
+        result.unwrap();
+    } else {
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Only 300 bytes can be wiped, which is less than 20.00% of the 1.95 KiB free on the volume"
+        );
+    }
+
+    // a dry run isn't refused, and still lists what would be wiped
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    for path in &test_run.hits {
+        assert_eq!(output.contains(&path.display().to_string()), allowed);
+        assert_eq!(path.exists(), !(wipe && allowed));
+    }
+}
+
+#[parameterized(
+    language = {
+        LanguageEnum::NodeModules, LanguageEnum::NodeModules,
+        LanguageEnum::Target, LanguageEnum::Target,
+    },
+    wipe = { false, true, false, true },
+)]
+fn run_with_skip_if_contains(language: LanguageEnum, wipe: bool) {
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let protected = test_run.hits.first().unwrap();
+    std::fs::File::create(protected.join(".nowipe")).unwrap();
+
+    let params = WipeParams {
+        wipe,
+        path: PathBuf::from(&test_run),
+        language,
+        skip_if_contains: vec![String::from(".nowipe")],
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    let lines = output.lines();
+    println!("{output}");
+
+    // the protected hit should be listed as such and never wiped
+    for path in &test_run.hits {
+        let expected = String::from(path.to_str().unwrap());
+        let mut lines = lines.clone();
+        let line = lines.find(|l| l.contains(&expected)).unwrap();
+
+        if path == protected {
+            assert!(line.contains("[Protected]"));
+            assert!(path.exists());
+        } else {
+            assert!(!line.contains("[Protected]"));
+            assert_eq!(path.exists(), !wipe);
+        }
+    }
+}
+
+#[parameterized(
+    language = {
+        LanguageEnum::NodeModules, LanguageEnum::NodeModules,
+        LanguageEnum::Target, LanguageEnum::Target,
+    },
+    wipe = { false, true, false, true },
+)]
+fn run_with_stage_and_commit_staged(language: LanguageEnum, wipe: bool) {
+    let test_run = TestRun::new(&language, 3, 0);
+
+    // the first staged name is already taken
+    let taken = test_run
+        .hits
+        .first()
+        .unwrap()
+        .with_file_name(format!("{language}.wipe-pending"));
+    std::fs::create_dir_all(&taken).unwrap();
+    std::fs::File::create(taken.join(".rustc_info.json")).unwrap();
+
+    let staged = test_run
+        .hits
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let suffix = if i == 0 {
+                ".wipe-pending.2"
+            } else {
+                ".wipe-pending"
+            };
+            path.with_file_name(format!("{language}{suffix}"))
+        })
+        .collect::<Vec<_>>();
+
+    let params = WipeParams {
+        wipe,
+        path: PathBuf::from(&test_run),
+        language: language.clone(),
+        stage: true,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    for (path, staged) in test_run.hits.iter().zip(&staged) {
+        assert_eq!(path.exists(), !wipe);
+        assert_eq!(staged.exists(), wipe);
+    }
+
+    let params = WipeParams {
+        wipe,
+        path: PathBuf::from(&test_run),
+        language,
+        commit_staged: true,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    // only the staged folders are wiped
+    for staged in &staged {
+        assert!(!staged.exists());
+    }
+    for path in &test_run.hits {
+        assert_eq!(path.exists(), !wipe);
+    }
+    assert_eq!(taken.exists(), !wipe);
+}
+
+#[parameterized(
+    language = {
+        LanguageEnum::NodeModules, LanguageEnum::NodeModules, LanguageEnum::NodeModules,
+        LanguageEnum::Target, LanguageEnum::Target, LanguageEnum::Target,
+    },
+    wipe = { false, true, true, false, true, true },
+    force = { true, false, true, true, false, true },
+)]
+fn run_requires_force(language: LanguageEnum, wipe: bool, force: bool) {
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let args = Args {
+        language: Some(language),
+        wipe,
+        force,
+        ..Args::default()
+    };
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        ..WipeParams::new(&args).unwrap()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    // only --wipe together with --force wipes anything
+    for path in &test_run.hits {
+        assert_eq!(path.exists(), !(wipe && force));
+    }
+
+    let expected = format!("{}", Paint::red("[WIPING]").bold());
+    assert_eq!(output.contains(&expected), wipe && force);
+
+    assert_eq!(output.contains("Nothing was wiped"), wipe && !force);
+}
+
+#[parameterized(with_root_size = { false, true })]
+fn run_with_root_size(with_root_size: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    for path in &test_run.hits {
+        std::fs::write(path.join("data"), [0; 100]).unwrap();
+    }
+    std::fs::write(test_run.misses.first().unwrap().join("data"), [0; 700]).unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        with_root_size,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    let expected = format!(
+        "The folders to wipe are {} of the {} in {}",
+        Paint::red("30.00%"),
+        Paint::cyan("1000 bytes"),
+        Paint::cyan(test_run.path.display()),
+    );
+    assert_eq!(output.contains(&expected), with_root_size);
+}
+
+#[parameterized(answer = { "y\n", "n\n", "" })]
+fn run_with_coalesce_siblings(answer: &str) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let workspace = test_run.path.join("workspace");
+    let targets = ["foo", "bar", "baz"]
+        .iter()
+        .map(|name| workspace.join(name).join("target"))
+        .collect::<Vec<_>>();
+
+    for target in &targets {
+        std::fs::create_dir_all(target).unwrap();
+        std::fs::write(target.join(".rustc_info.json"), [0; 100]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        coalesce_siblings: true,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params)
+        .with_input(answer.as_bytes())
+        .run()
+        .unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    let expected = format!(
+        r#"3 "{}" folders under {}, {} total, delete all? [y/N] "#,
+        Paint::cyan("target"),
+        Paint::cyan(workspace.display()),
+        Paint::red("300 bytes"),
+    );
+    assert_eq!(output.matches(&expected).count(), 1);
+    assert_eq!(output.matches("delete all?").count(), 1);
+
+    let confirmed = answer == "y\n";
+    for target in &targets {
+        assert_eq!(target.exists(), !confirmed);
+    }
+
+    let declined = format!("{}", Paint::yellow("[Declined]"));
+    assert_eq!(
+        output.matches(&declined).count(),
+        if confirmed { 0 } else { targets.len() }
+    );
+}
+
+#[parameterized(
+    prompt_default = { PromptDefaultEnum::No, PromptDefaultEnum::Yes },
+    choices = { "[y/N]", "[Y/n]" },
+)]
+fn run_with_prompt_default(prompt_default: PromptDefaultEnum, choices: &str) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 0, 0);
+
+    let targets = ["foo", "bar"]
+        .iter()
+        .map(|name| test_run.path.join("workspace").join(name).join("target"))
+        .collect::<Vec<_>>();
+
+    for target in &targets {
+        std::fs::create_dir_all(target).unwrap();
+        std::fs::write(target.join(".rustc_info.json"), [0; 100]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        coalesce_siblings: true,
+        prompt_default,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params)
+        .with_input("\n".as_bytes())
+        .run()
+        .unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    assert!(output.contains(&format!("delete all? {choices} ")));
+
+    let confirmed = prompt_default == PromptDefaultEnum::Yes;
+    for target in &targets {
+        assert_eq!(target.exists(), !confirmed);
+    }
+}
+
+#[test]
+fn run_with_path_changed_type() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+    let swapped = test_run.hits[0].clone();
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        ..WipeParams::default()
+    };
+
+    // the folder is replaced by a file between finding and wiping it
+    let hooks = Hooks {
+        before_delete: Some(Box::new(|path: &Path| {
+            if path == swapped {
+                std::fs::remove_dir_all(path).unwrap();
+                std::fs::write(path, "not a folder").unwrap();
+            }
+        })),
+        ..Hooks::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params)
+        .with_hooks(hooks)
+        .run()
+        .unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    let expected = format!("{}", Paint::yellow("[Path changed type, skipping]"));
+    assert_eq!(output.matches(&expected).count(), 1);
+    assert!(output
+        .lines()
+        .any(|line| line.contains(&swapped.display().to_string()) && line.contains(&expected)));
+
+    assert!(swapped.is_file());
+    for path in &test_run.hits[1..] {
+        assert!(!path.exists());
+    }
+}
+
+#[parameterized(wipe = { false, true })]
+fn run_with_head(wipe: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 5, 0);
+
+    for path in &test_run.hits {
+        std::fs::write(path.join("data"), [0; 100]).unwrap();
+    }
+
+    let params = WipeParams {
+        wipe,
+        path: PathBuf::from(&test_run),
+        language,
+        head: Some(2),
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    let shown = test_run
+        .hits
+        .iter()
+        .filter(|path| output.contains(&path.display().to_string()))
+        .count();
+    assert_eq!(shown, 2);
+    assert!(output.contains("... and 3 more (use --all to show)"));
+
+    // the totals and the wipe still cover all of them
+    assert!(output
+        .lines()
+        .any(|line| line.contains("500 bytes")
+            && (line.contains("Can wipe") || line.contains("Wiped"))));
+    for path in &test_run.hits {
+        assert_eq!(path.exists(), !wipe);
+    }
+}
+
+#[parameterized(atomic = { false, true })]
+fn run_with_atomic(atomic: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        atomic,
+        ..WipeParams::default()
+    };
+
+    let mut removed = Vec::new();
+    let hooks = Hooks {
+        before_remove: Some(Box::new(|path: &Path| {
+            let original = test_run
+                .hits
+                .iter()
+                .find(|hit| hit.parent() == path.parent())
+                .unwrap();
+
+            // the original path is already gone while the folder is being removed
+            assert!(path.is_dir());
+            assert_eq!(original.exists(), !atomic);
+            removed.push((original.clone(), path.to_path_buf()));
+        })),
+        ..Hooks::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params)
+        .with_hooks(hooks)
+        .run()
+        .unwrap();
+
+    assert_eq!(removed.len(), test_run.hits.len());
+
+    for (original, path) in &removed {
+        assert_eq!(original != path, atomic);
+        assert!(!original.exists());
+        assert!(!path.exists());
+    }
+}
+
+#[parameterized(auto_units = { false, true })]
+fn run_with_auto_units(auto_units: bool) {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 2, 0);
+
+    let small = &test_run.hits[0];
+    let large = &test_run.hits[1];
+    std::fs::write(small.join("data"), vec![0; 900 * 1024]).unwrap();
+    // sparse, so it doesn't take up the space
+    std::fs::File::create(large.join("data"))
+        .unwrap()
+        .set_len(9 * 1024_u64.pow(3))
+        .unwrap();
+
+    let params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        auto_units,
+        ..WipeParams::default()
+    };
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    let output = std::str::from_utf8(buff.get_ref()).unwrap();
+    println!("{output}");
+
+    let row = |path: &PathBuf| {
+        output
+            .lines()
+            .find(|line| line.ends_with(&path.display().to_string()))
+            .unwrap()
+            .to_string()
+    };
+
+    if auto_units {
+        assert!(row(small).contains(&format!("{:>SPACING_SIZE$}", "900.00 KiB")));
+        assert!(row(large).contains(&format!("{:>SPACING_SIZE$}", "9.00 GiB")));
+        assert!(output.contains(&format!("{:>SPACING_SIZE$}", Paint::cyan("Size"))));
+    } else {
+        assert!(row(small).contains(&format!("{:>SPACING_SIZE$}", "0")));
+        assert!(row(large).contains(&format!("{:>SPACING_SIZE$}", "9,216")));
+        assert!(output.contains(&format!("{:>SPACING_SIZE$}", Paint::cyan("Size (MB)"))));
+    }
+
+    // the totals are always in their own unit
+    assert!(output.contains("9.00 GiB"));
+}
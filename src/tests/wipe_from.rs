@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use crate::command::{FormatEnum, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
+use crate::tests::report_json::run_json;
+use crate::wipe::WipeParams;
+
+#[test]
+fn run_with_wipe_from() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 1);
+
+    let mut params = WipeParams {
+        path: PathBuf::from(&test_run),
+        language,
+        ignores: test_run.ignores.clone(),
+        format: FormatEnum::Json,
+        ..WipeParams::default()
+    };
+
+    let saved = test_run.path.join("saved.json");
+    let scan = run_json(&params);
+    std::fs::write(&saved, scan.to_string()).unwrap();
+
+    let [present, gone, invalid] = &test_run.hits[..] else {
+        unreachable!()
+    };
+    std::fs::remove_dir_all(gone).unwrap();
+    std::fs::remove_file(invalid.join(".rustc_info.json")).unwrap();
+
+    params.wipe = true;
+    params.ignores = Vec::new();
+    params.wipe_from = Some(saved);
+
+    let json = run_json(&params);
+    let folders = json["folders"].as_array().unwrap();
+    let folder = |path: &PathBuf| {
+        folders
+            .iter()
+            .find(|f| f["path"] == path.display().to_string())
+            .unwrap()
+    };
+
+    // the ignored folder wasn't saved to be wiped
+    assert_eq!(folders.len(), 3);
+
+    assert_eq!(folder(present)["still_present"], true);
+    assert_eq!(folder(present)["still_valid"], true);
+    assert!(folder(present)["skipped"].is_null());
+    assert!(!present.exists());
+
+    assert_eq!(folder(gone)["still_present"], false);
+    assert_eq!(folder(gone)["still_valid"], false);
+    assert_eq!(folder(gone)["skipped"], "stale");
+
+    assert_eq!(folder(invalid)["still_present"], true);
+    assert_eq!(folder(invalid)["still_valid"], false);
+    assert_eq!(folder(invalid)["skipped"], "stale");
+    assert!(invalid.exists());
+
+    assert_eq!(json["summary"]["stale"], 2);
+    assert!(test_run.ignores[0].exists());
+}
@@ -0,0 +1,61 @@
+use std::io::{self, Cursor};
+use std::path::PathBuf;
+
+use crate::command::LanguageEnum;
+use crate::tests::helpers::test_run::TestRun;
+use crate::wipe::{Wipe, WipeParams};
+use crate::wipe_lock::WipeLock;
+
+#[test]
+fn lock_is_exclusive() {
+    let test_run = TestRun::new(&LanguageEnum::Target, 0, 0);
+    let lock_path = test_run.path.join("wipe.lock");
+
+    let lock = WipeLock::acquire(&lock_path).unwrap();
+
+    let error = WipeLock::acquire(&lock_path).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+    assert!(error.to_string().contains("another wipe is in progress"));
+
+    drop(lock);
+
+    WipeLock::acquire(&lock_path).unwrap();
+}
+
+#[test]
+fn run_with_held_lock() {
+    let language = LanguageEnum::Target;
+    let test_run = TestRun::new(&language, 3, 0);
+    let lock_path = test_run.path.join("wipe.lock");
+
+    let params = WipeParams {
+        wipe: true,
+        path: PathBuf::from(&test_run),
+        language,
+        lock: Some(lock_path.clone()),
+        ..WipeParams::default()
+    };
+
+    let lock = WipeLock::acquire(&lock_path).unwrap();
+
+    let mut buff = Cursor::new(Vec::new());
+    let error = Wipe::new(&mut buff, &params).run().unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+    assert!(buff.get_ref().is_empty());
+    for path in &test_run.hits {
+        assert!(path.exists());
+    }
+
+    drop(lock);
+
+    let mut buff = Cursor::new(Vec::new());
+    Wipe::new(&mut buff, &params).run().unwrap();
+
+    for path in &test_run.hits {
+        assert!(!path.exists());
+    }
+
+    // released once the run is over
+    WipeLock::acquire(&lock_path).unwrap();
+}
@@ -1,16 +1,20 @@
 use parameterized::parameterized;
+use std::io::IsTerminal;
 use std::path::PathBuf;
 
 use crate::command::{Args, LanguageEnum};
+use crate::tests::helpers::test_run::TestRun;
 use crate::wipe::WipeParams;
 
 #[parameterized(
     args = {
-        Args { wipe: false, language: LanguageEnum::NodeModules, ignores: Vec::new() },
-        Args { wipe: true, language: LanguageEnum::NodeModules, ignores: Vec::new() },
-        Args { wipe: false, language: LanguageEnum::Node, ignores: Vec::new() },
-        Args { wipe: true, language: LanguageEnum::Node, ignores: Vec::new() },
-        Args { wipe: true, language: LanguageEnum::Node, ignores: vec![PathBuf::from("example/path")] },
+        Args { wipe: false, language: Some(LanguageEnum::NodeModules), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, language: Some(LanguageEnum::NodeModules), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: false, language: Some(LanguageEnum::Node), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, language: Some(LanguageEnum::Node), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, force: true, language: Some(LanguageEnum::Node), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: false, force: true, language: Some(LanguageEnum::Node), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, language: Some(LanguageEnum::Node), ignores: vec![PathBuf::from("example/path")], ..Default::default() },
     },
 )]
 fn node(args: Args) {
@@ -19,21 +23,76 @@ fn node(args: Args) {
     assert_eq!(
         params,
         WipeParams {
-            wipe: args.wipe,
+            wipe: args.wipe && args.force,
+            wipe_unconfirmed: args.wipe && !args.force,
             path: std::env::current_dir().unwrap(),
-            language: args.language,
+            language: args.language.unwrap(),
             ignores: args.ignores,
+            format: args.format,
+            expect_at_least: args.expect_at_least,
+            skip_if_contains: args.skip_if_contains,
+            manifest: args.manifest,
+            stage: args.stage,
+            commit_staged: args.commit_staged,
+            max_inflight: args.max_inflight,
+            timings_per_folder: args.timings_per_folder,
+            include_pnpm_store: args.include_pnpm_store,
+            per_project_report: args.per_project_report,
+            with_root_size: args.with_root_size,
+            path_components: args.path_components,
+            chmod_parents: args.chmod_parents,
+            summary_json: args.summary_json,
+            with_shallow: args.with_shallow,
+            lock: args.lock,
+            ascii: args.ascii,
+            show_symlinks: args.show_symlinks,
+            rebuild_cost: args.rebuild_cost,
+            coalesce_siblings: args.coalesce_siblings,
+            events: args.events,
+            size_precision: args.size_precision,
+            max_depth: args.max_depth,
+            exclude_if_under_git_lfs: args.exclude_if_under_git_lfs,
+            auto_units: args.auto_units,
+            detect_monorepo_tool: args.detect_monorepo_tool,
+            wipe_from: args.wipe_from,
+            buffered: args.buffered || !std::io::stdout().is_terminal(),
+            min_reclaim_ratio: args.min_reclaim_ratio,
+            progress: args.progress,
+            atomic: args.atomic,
+            min_size: args.min_size,
+            head: args.head.filter(|_| !args.all),
+            shard_output: args.shard_output,
+            shard_size: args.shard_size,
+            snapshot: args.snapshot,
+            fastest_growing: args.fastest_growing,
+            with_volume: args.with_volume,
+            prompt_default: args.prompt_default,
+            exclude_regex: args.exclude_regex,
+            benchmark: args.benchmark.then_some(args.benchmark_size),
+            benchmark_dir: args.benchmark_dir,
+            deny_file: args.deny_file,
+            with_times: args.with_times,
+            verify: args.verify,
+            min_depth: args.min_depth,
+            sample: args.sample,
+            continue_on_panic: args.continue_on_panic,
+            keep_inside: args.keep_inside.clone(),
+            concise_errors: args.concise_errors,
+            verbose: args.verbose,
+            output_version: args.output_version,
         }
     );
 }
 
 #[parameterized(
     args = {
-        Args { wipe: false, language: LanguageEnum::Target, ignores: Vec::new() },
-        Args { wipe: true, language: LanguageEnum::Target, ignores: Vec::new() },
-        Args { wipe: false, language: LanguageEnum::Rust, ignores: Vec::new() },
-        Args { wipe: true, language: LanguageEnum::Rust, ignores: Vec::new() },
-        Args { wipe: true, language: LanguageEnum::Rust, ignores: vec![PathBuf::from("example/path")] },
+        Args { wipe: false, language: Some(LanguageEnum::Target), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, language: Some(LanguageEnum::Target), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: false, language: Some(LanguageEnum::Rust), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, language: Some(LanguageEnum::Rust), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, force: true, language: Some(LanguageEnum::Rust), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: false, force: true, language: Some(LanguageEnum::Rust), ignores: Vec::new(), ..Default::default() },
+        Args { wipe: true, language: Some(LanguageEnum::Rust), ignores: vec![PathBuf::from("example/path")], ..Default::default() },
     },
 )]
 fn rust(args: Args) {
@@ -42,10 +101,100 @@ fn rust(args: Args) {
     assert_eq!(
         params,
         WipeParams {
-            wipe: args.wipe,
+            wipe: args.wipe && args.force,
+            wipe_unconfirmed: args.wipe && !args.force,
             path: std::env::current_dir().unwrap(),
-            language: args.language,
+            language: args.language.unwrap(),
             ignores: args.ignores,
+            format: args.format,
+            expect_at_least: args.expect_at_least,
+            skip_if_contains: args.skip_if_contains,
+            manifest: args.manifest,
+            stage: args.stage,
+            commit_staged: args.commit_staged,
+            max_inflight: args.max_inflight,
+            timings_per_folder: args.timings_per_folder,
+            include_pnpm_store: args.include_pnpm_store,
+            per_project_report: args.per_project_report,
+            with_root_size: args.with_root_size,
+            path_components: args.path_components,
+            chmod_parents: args.chmod_parents,
+            summary_json: args.summary_json,
+            with_shallow: args.with_shallow,
+            lock: args.lock,
+            ascii: args.ascii,
+            show_symlinks: args.show_symlinks,
+            rebuild_cost: args.rebuild_cost,
+            coalesce_siblings: args.coalesce_siblings,
+            events: args.events,
+            size_precision: args.size_precision,
+            max_depth: args.max_depth,
+            exclude_if_under_git_lfs: args.exclude_if_under_git_lfs,
+            auto_units: args.auto_units,
+            detect_monorepo_tool: args.detect_monorepo_tool,
+            wipe_from: args.wipe_from,
+            buffered: args.buffered || !std::io::stdout().is_terminal(),
+            min_reclaim_ratio: args.min_reclaim_ratio,
+            progress: args.progress,
+            atomic: args.atomic,
+            min_size: args.min_size,
+            head: args.head.filter(|_| !args.all),
+            shard_output: args.shard_output,
+            shard_size: args.shard_size,
+            snapshot: args.snapshot,
+            fastest_growing: args.fastest_growing,
+            with_volume: args.with_volume,
+            prompt_default: args.prompt_default,
+            exclude_regex: args.exclude_regex,
+            benchmark: args.benchmark.then_some(args.benchmark_size),
+            benchmark_dir: args.benchmark_dir,
+            deny_file: args.deny_file,
+            with_times: args.with_times,
+            verify: args.verify,
+            min_depth: args.min_depth,
+            sample: args.sample,
+            continue_on_panic: args.continue_on_panic,
+            keep_inside: args.keep_inside.clone(),
+            concise_errors: args.concise_errors,
+            verbose: args.verbose,
+            output_version: args.output_version,
         }
     );
 }
+
+#[parameterized(git_root = { false, true })]
+fn search_root_from_nested_folder(git_root: bool) {
+    let test_run = TestRun::new(&LanguageEnum::Target, 0, 0);
+    let repo = test_run.path.join("repo");
+    let nested = repo.join("crates").join("foo").join("src");
+    std::fs::create_dir_all(repo.join(".git")).unwrap();
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let args = Args {
+        git_root,
+        ..Default::default()
+    };
+
+    let root = WipeParams::search_root(&args, nested.clone()).unwrap();
+
+    assert_eq!(root, if git_root { repo } else { nested });
+}
+
+#[parameterized(git_root_fallback = { false, true })]
+fn search_root_outside_git_repo(git_root_fallback: bool) {
+    let test_run = TestRun::new(&LanguageEnum::Target, 0, 0);
+
+    let args = Args {
+        git_root: true,
+        git_root_fallback,
+        ..Default::default()
+    };
+
+    let root = WipeParams::search_root(&args, test_run.path.clone());
+
+    if git_root_fallback {
+        assert_eq!(root.unwrap(), test_run.path);
+    } else {
+        assert_eq!(root.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    }
+}
@@ -25,6 +25,7 @@ mod wipe_permissions_tests {
             path: PathBuf::from(&test_run),
             language,
             ignores: Vec::new(),
+            ..WipeParams::default()
         };
 
         let first_hit = test_run.hits.first().unwrap().clone();
@@ -73,6 +74,7 @@ mod wipe_permissions_tests {
             path: PathBuf::from(&test_run),
             language,
             ignores: Vec::new(),
+            ..WipeParams::default()
         };
 
         let first_hit = test_run.hits.first().unwrap().clone();
@@ -104,3 +106,201 @@ mod wipe_permissions_tests {
         fs::set_permissions(&first_hit, permissions).unwrap();
     }
 }
+
+#[cfg(target_os = "linux")]
+mod wipe_permissions_errors_tests {
+    use parameterized::parameterized;
+    use std::fs;
+    use std::io::Cursor;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+
+    use crate::command::{FormatEnum, LanguageEnum};
+    use crate::tests::helpers::test_run::TestRun;
+    use crate::tests::report_json::run_json;
+    use crate::wipe::{Wipe, WipeParams};
+
+    #[parameterized(
+        language = {
+            LanguageEnum::NodeModules, LanguageEnum::Target,
+        },
+    )]
+    fn json_errors(language: LanguageEnum) {
+        let test_run = TestRun::new(&language, 3, 0);
+
+        let params = WipeParams {
+            wipe: true,
+            path: PathBuf::from(&test_run),
+            language,
+            format: FormatEnum::Json,
+            ..WipeParams::default()
+        };
+
+        // the folder itself can be emptied, but not removed from its parent
+        let undeletable = test_run.hits.first().unwrap().clone();
+        let parent = undeletable.parent().unwrap().to_path_buf();
+        fs::set_permissions(&parent, fs::Permissions::from_mode(0o555)).unwrap();
+
+        // the walk can't look inside
+        let unreadable = test_run.misses.first().unwrap().clone();
+        fs::set_permissions(&unreadable, fs::Permissions::from_mode(0o000)).unwrap();
+
+        let json = run_json(&params);
+
+        // revert chmod
+        fs::set_permissions(&parent, fs::Permissions::from_mode(0o777)).unwrap();
+        fs::set_permissions(&unreadable, fs::Permissions::from_mode(0o777)).unwrap();
+
+        let errors = json["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+
+        let error = errors.iter().find(|e| e["phase"] == "delete").unwrap();
+        assert_eq!(error["path"], undeletable.display().to_string());
+        assert_eq!(
+            error["message"],
+            format!("parent directory not writable: {}", parent.display())
+        );
+        assert!(undeletable.exists());
+
+        let error = errors.iter().find(|e| e["phase"] == "walk").unwrap();
+        assert_eq!(error["path"], unreadable.display().to_string());
+
+        for path in test_run.hits.iter().skip(1) {
+            assert!(!path.exists());
+        }
+    }
+
+    #[parameterized(
+        concise_errors = { false, true, true },
+        verbose = { false, false, true },
+    )]
+    fn concise_errors(concise_errors: bool, verbose: bool) {
+        let language = LanguageEnum::Target;
+        let test_run = TestRun::new(&language, 1, 0);
+
+        let params = WipeParams {
+            path: PathBuf::from(&test_run),
+            language,
+            concise_errors,
+            verbose,
+            ..WipeParams::default()
+        };
+
+        let unreadable = (0..42)
+            .map(|i| test_run.path.join(format!("unreadable_{i}")))
+            .collect::<Vec<_>>();
+
+        for path in &unreadable {
+            fs::create_dir(path).unwrap();
+            fs::set_permissions(path, fs::Permissions::from_mode(0o000)).unwrap();
+        }
+
+        let mut buff = Cursor::new(Vec::new());
+        Wipe::new(&mut buff, &params).run().unwrap();
+
+        for path in &unreadable {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o777)).unwrap();
+        }
+
+        let output = std::str::from_utf8(buff.get_ref()).unwrap();
+        println!("{output}");
+
+        let summaries = output
+            .lines()
+            .filter(|l| l.contains("42 directories skipped (permission denied)"))
+            .count();
+        assert_eq!(summaries, usize::from(concise_errors));
+
+        for path in &unreadable {
+            let listed = output.contains(path.to_str().unwrap());
+            assert_eq!(listed, !concise_errors || verbose);
+        }
+    }
+
+    // with 0o575 only the group can write, and not its owner
+    #[parameterized(
+        mode = { 0o555, 0o555, 0o575, 0o575 },
+        chmod_parents = { false, true, false, true },
+    )]
+    fn readonly_parent(mode: u32, chmod_parents: bool) {
+        let language = LanguageEnum::Target;
+        let test_run = TestRun::new(&language, 1, 0);
+
+        let params = WipeParams {
+            wipe: true,
+            path: PathBuf::from(&test_run),
+            language,
+            format: FormatEnum::Json,
+            chmod_parents,
+            ..WipeParams::default()
+        };
+
+        let folder = test_run.hits.first().unwrap().clone();
+        let parent = folder.parent().unwrap().to_path_buf();
+        fs::set_permissions(&parent, fs::Permissions::from_mode(mode)).unwrap();
+
+        let json = run_json(&params);
+
+        let mode_after = fs::metadata(&parent).unwrap().permissions().mode();
+
+        // revert chmod
+        fs::set_permissions(&parent, fs::Permissions::from_mode(0o777)).unwrap();
+
+        // the parent is left the way it was
+        assert_eq!(mode_after & 0o777, mode);
+
+        let errors = json["errors"].as_array().unwrap();
+
+        if chmod_parents {
+            assert!(errors.is_empty());
+            assert!(!folder.exists());
+        } else {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(
+                errors[0]["message"],
+                format!("parent directory not writable: {}", parent.display())
+            );
+            assert!(folder.exists());
+        }
+    }
+
+    #[test]
+    fn atomic_with_readonly_child() {
+        let language = LanguageEnum::Target;
+        let test_run = TestRun::new(&language, 1, 0);
+
+        let params = WipeParams {
+            wipe: true,
+            path: PathBuf::from(&test_run),
+            language,
+            format: FormatEnum::Json,
+            atomic: true,
+            ..WipeParams::default()
+        };
+
+        // the folder is renamed aside, but then can't be removed
+        let folder = test_run.hits.first().unwrap().clone();
+        let child = folder.join("debug");
+        fs::create_dir(&child).unwrap();
+        fs::write(child.join("data"), [0; 100]).unwrap();
+        fs::set_permissions(&child, fs::Permissions::from_mode(0o555)).unwrap();
+
+        let json = run_json(&params);
+
+        // revert chmod
+        fs::set_permissions(&child, fs::Permissions::from_mode(0o777)).unwrap();
+
+        // it is put back where it was found, and reported there
+        assert!(child.join("data").exists());
+        let siblings = fs::read_dir(folder.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert!(siblings.iter().all(|name| !name.contains(".wiping.")));
+
+        let folders = json["folders"].as_array().unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0]["path"], folder.display().to_string());
+        assert!(folders[0]["error"].is_string());
+    }
+}
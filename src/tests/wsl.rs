@@ -0,0 +1,38 @@
+use parameterized::parameterized;
+use std::path::Path;
+
+use crate::wsl::{is_wsl, windows_drive, windows_drive_warning};
+
+const WSL_VERSION: &str = "Linux version 5.15.153.1-microsoft-standard-WSL2 (root@65c757a075e2) \
+    (gcc (GCC) 11.2.0, GNU ld (GNU Binutils) 2.37) #1 SMP Fri Mar 29 23:14:13 UTC 2024";
+const LINUX_VERSION: &str = "Linux version 6.8.0-45-generic (buildd@lcy02-amd64-075) \
+    (x86_64-linux-gnu-gcc-13 (Ubuntu 13.2.0-23ubuntu4) 13.2.0) #45-Ubuntu SMP";
+
+#[parameterized(proc_version = { WSL_VERSION, LINUX_VERSION }, expected = { true, false })]
+fn detects_wsl(proc_version: &str, expected: bool) {
+    assert_eq!(is_wsl(proc_version), expected);
+}
+
+#[parameterized(
+    path = { "/mnt/c", "/mnt/d/projects", "/mnt", "/mnt/data", "/home/mnt/c", "mnt/c" },
+    expected = { Some('c'), Some('d'), None, None, None, None }
+)]
+fn detects_windows_drive(path: &str, expected: Option<char>) {
+    assert_eq!(windows_drive(Path::new(path)), expected);
+}
+
+#[test]
+fn warns_for_windows_drive_on_wsl() {
+    let warning = windows_drive_warning(Path::new("/mnt/c/projects"), Some(WSL_VERSION)).unwrap();
+
+    assert!(warning.contains("/mnt/c/projects"));
+    assert!(warning.contains(r"C:\"));
+}
+
+#[parameterized(
+    path = { "/mnt/c/projects", "/home/user/projects", "/mnt/c/projects" },
+    proc_version = { Some(LINUX_VERSION), Some(WSL_VERSION), None }
+)]
+fn does_not_warn(path: &str, proc_version: Option<&str>) {
+    assert_eq!(windows_drive_warning(Path::new(path), proc_version), None);
+}
@@ -0,0 +1,41 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The creation and the modification time of the folder itself, not of the files inside it.
+/// Platforms and filesystems that don't record the creation time leave it out.
+pub fn folder_times(path: &Path) -> (Option<String>, Option<String>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return (None, None);
+    };
+
+    (
+        metadata.created().ok().and_then(iso8601),
+        metadata.modified().ok().and_then(iso8601),
+    )
+}
+
+/// Formats the time as ISO 8601 in UTC, to the second, e.g. `2024-03-09T14:05:00Z`.
+pub fn iso8601(time: SystemTime) -> Option<String> {
+    let secs = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // the days since the epoch to a civil date, after
+    // http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    ))
+}
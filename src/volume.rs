@@ -0,0 +1,35 @@
+use std::path::Path;
+
+/// Identifies the filesystem that holds `path` by its mount point, or by its device id when the
+/// mount point can't be told.
+#[cfg(unix)]
+pub fn volume(path: &Path) -> Option<String> {
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+
+    let device = fs::metadata(path).ok()?.dev();
+
+    match mount_point(path, device) {
+        Some(mount_point) => Some(mount_point.display().to_string()),
+        None => Some(format!("dev:{device}")),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn volume(_path: &Path) -> Option<String> {
+    None
+}
+
+/// The topmost ancestor of `path` that is still on `device`.
+#[cfg(unix)]
+fn mount_point(path: &Path, device: u64) -> Option<std::path::PathBuf> {
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+
+    let path = fs::canonicalize(path).ok()?;
+
+    path.ancestors()
+        .take_while(|ancestor| fs::metadata(ancestor).is_ok_and(|data| data.dev() == device))
+        .last()
+        .map(Path::to_path_buf)
+}
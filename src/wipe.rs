@@ -1,36 +1,247 @@
+use num_format::{Locale, ToFormattedString};
+use std::borrow::Cow;
 use std::fs;
-use std::io;
-use std::path::PathBuf;
-use std::{env, fmt::Display};
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{env, fmt, fmt::Display};
 use yansi::Paint;
 
+use crate::ascii::to_ascii;
+use crate::benchmark;
+use crate::cargo_lock;
 use crate::command::DirectoryEnum;
-use crate::command::{Args, LanguageEnum};
-use crate::dir_helpers::{dir_size, get_paths_to_delete, DirInfo};
+use crate::command::{
+    Args, ByteSize, EventsEnum, FormatEnum, LanguageEnum, OutputVersionEnum, PathRegex,
+    PromptDefaultEnum,
+};
+use crate::deny_file;
+use crate::dir_helpers::{
+    dir_size, dir_sizes_ordered, get_paths_to_delete, get_staged_paths, has_leftovers,
+    is_valid_match, kept_size, remove_dir, remove_dir_except, rename_aside, stage_path, DepthBand,
+    DirInfo,
+};
+use crate::free_space;
+use crate::fs_limiter::FsLimiter;
+use crate::git;
+use crate::lfs;
+use crate::manifest::write_manifest;
+use crate::monorepo;
+use crate::pnpm;
+use crate::report::events::{self, Event};
+use crate::report::{
+    group_walk_errors, matched_name, path_components, ErrorPhase, ErrorReport, FolderReport,
+    Report, SkipReason, Status, SummaryReport, Totals,
+};
+use crate::report::{json, per_project, shards};
+use crate::times;
+use crate::volume;
+use crate::wipe_lock::WipeLock;
+use crate::wsl;
 
 pub const SPACING_FILES: usize = 12;
 pub const SPACING_SIZE: usize = 18;
 pub const SPACING_PATH: usize = 9;
+pub const SPACING_SYMLINKS: usize = 12;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq)]
 pub struct WipeParams {
     pub wipe: bool,
+    /// `--wipe` was asked for, but not confirmed by `--force`
+    pub wipe_unconfirmed: bool,
     pub path: PathBuf,
     pub language: LanguageEnum,
     pub ignores: Vec<PathBuf>,
+    pub format: FormatEnum,
+    pub expect_at_least: Option<ByteSize>,
+    pub skip_if_contains: Vec<String>,
+    pub manifest: Option<PathBuf>,
+    pub stage: bool,
+    pub commit_staged: bool,
+    pub max_inflight: Option<usize>,
+    pub timings_per_folder: bool,
+    pub include_pnpm_store: bool,
+    pub per_project_report: Option<PathBuf>,
+    pub with_root_size: bool,
+    pub path_components: bool,
+    pub chmod_parents: bool,
+    pub summary_json: Option<PathBuf>,
+    pub with_shallow: bool,
+    pub lock: Option<PathBuf>,
+    pub ascii: bool,
+    pub show_symlinks: bool,
+    pub rebuild_cost: bool,
+    pub coalesce_siblings: bool,
+    pub events: Option<EventsEnum>,
+    pub size_precision: Option<usize>,
+    pub max_depth: Option<usize>,
+    pub exclude_if_under_git_lfs: bool,
+    pub auto_units: bool,
+    pub detect_monorepo_tool: bool,
+    pub wipe_from: Option<PathBuf>,
+    pub buffered: bool,
+    pub min_reclaim_ratio: Option<f64>,
+    pub progress: bool,
+    pub atomic: bool,
+    pub min_size: Option<ByteSize>,
+    pub head: Option<usize>,
+    pub shard_output: Option<PathBuf>,
+    pub shard_size: usize,
+    pub snapshot: Option<PathBuf>,
+    pub fastest_growing: Option<usize>,
+    pub with_volume: bool,
+    pub prompt_default: PromptDefaultEnum,
+    pub exclude_regex: Vec<PathRegex>,
+    /// The number of projects of the synthetic tree to time, instead of searching
+    pub benchmark: Option<usize>,
+    /// Where the synthetic tree of `benchmark` is created, a temporary folder by default
+    pub benchmark_dir: Option<PathBuf>,
+    pub deny_file: Option<PathBuf>,
+    pub with_times: bool,
+    pub verify: bool,
+    pub min_depth: Option<usize>,
+    pub sample: Option<usize>,
+    pub continue_on_panic: bool,
+    pub keep_inside: Vec<PathBuf>,
+    pub concise_errors: bool,
+    pub verbose: bool,
+    pub output_version: OutputVersionEnum,
 }
 
 impl WipeParams {
     pub fn new(args: &Args) -> io::Result<Self> {
-        let path = env::current_dir()?;
+        let path = Self::search_root(args, env::current_dir()?)?;
 
         Ok(Self {
-            wipe: args.wipe,
+            wipe: args.wipe && args.force,
+            wipe_unconfirmed: args.wipe && !args.force,
             path,
-            language: args.language.clone(),
+            // only `--benchmark` goes without one
+            language: args.language.clone().unwrap_or_default(),
             ignores: args.ignores.clone(),
+            format: args.format,
+            expect_at_least: args.expect_at_least,
+            skip_if_contains: args.skip_if_contains.clone(),
+            manifest: args.manifest.clone(),
+            stage: args.stage,
+            commit_staged: args.commit_staged,
+            max_inflight: args.max_inflight,
+            timings_per_folder: args.timings_per_folder,
+            include_pnpm_store: args.include_pnpm_store,
+            per_project_report: args.per_project_report.clone(),
+            with_root_size: args.with_root_size,
+            path_components: args.path_components,
+            chmod_parents: args.chmod_parents,
+            summary_json: args.summary_json.clone(),
+            with_shallow: args.with_shallow,
+            lock: args.lock.clone(),
+            ascii: args.ascii,
+            show_symlinks: args.show_symlinks,
+            rebuild_cost: args.rebuild_cost,
+            coalesce_siblings: args.coalesce_siblings,
+            events: args.events,
+            size_precision: args.size_precision,
+            max_depth: if args.no_recurse {
+                Some(1)
+            } else {
+                args.max_depth
+            },
+            exclude_if_under_git_lfs: args.exclude_if_under_git_lfs,
+            auto_units: args.auto_units,
+            detect_monorepo_tool: args.detect_monorepo_tool,
+            wipe_from: args.wipe_from.clone(),
+            buffered: args.buffered || !io::stdout().is_terminal(),
+            min_reclaim_ratio: args.min_reclaim_ratio,
+            progress: args.progress,
+            atomic: args.atomic,
+            min_size: args.min_size,
+            head: args.head.filter(|_| !args.all),
+            shard_output: args.shard_output.clone(),
+            shard_size: args.shard_size,
+            snapshot: args.snapshot.clone(),
+            fastest_growing: args.fastest_growing,
+            with_volume: args.with_volume,
+            prompt_default: args.prompt_default,
+            exclude_regex: args.exclude_regex.clone(),
+            benchmark: args.benchmark.then_some(args.benchmark_size),
+            benchmark_dir: args.benchmark_dir.clone(),
+            deny_file: args.deny_file.clone(),
+            with_times: args.with_times,
+            verify: args.verify,
+            min_depth: args.min_depth,
+            sample: args.sample,
+            continue_on_panic: args.continue_on_panic,
+            keep_inside: args.keep_inside.clone(),
+            concise_errors: args.concise_errors,
+            verbose: args.verbose,
+            output_version: args.output_version,
         })
     }
+
+    /// Whether the output is meant for machines, which is when colors have no place in it.
+    pub fn structured(&self) -> bool {
+        self.format != FormatEnum::Text || self.events.is_some()
+    }
+
+    /// The folder to search in, which is the current one unless `--git-root` is used.
+    pub fn search_root(args: &Args, current_dir: PathBuf) -> io::Result<PathBuf> {
+        if !args.git_root {
+            return Ok(current_dir);
+        }
+
+        match git::repo_root(&current_dir) {
+            Some(root) => Ok(root),
+            None if args.git_root_fallback => Ok(current_dir),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No git repository found in {}", current_dir.display()),
+            )),
+        }
+    }
+}
+
+pub type PathHook<'a> = Box<dyn FnMut(&Path) + 'a>;
+pub type FreeSpaceHook<'a> = Box<dyn Fn(&Path) -> io::Result<usize> + 'a>;
+
+/// Callbacks around the deletion of each folder, for tests to step in at the right moment.
+#[derive(Default)]
+pub struct Hooks<'a> {
+    pub before_delete: Option<PathHook<'a>>,
+    /// Called with the path that is about to be removed, after any renaming
+    pub before_remove: Option<PathHook<'a>>,
+    /// Called with the original path of each folder once it is wiped
+    pub after_delete: Option<PathHook<'a>>,
+    /// Replaces the query of the free space on the volume
+    pub free_space: Option<FreeSpaceHook<'a>>,
+}
+
+impl fmt::Debug for Hooks<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("before_delete", &self.before_delete.is_some())
+            .field("before_remove", &self.before_remove.is_some())
+            .field("after_delete", &self.after_delete.is_some())
+            .field("free_space", &self.free_space.is_some())
+            .finish()
+    }
+}
+
+/// What became of a folder that was going to be wiped.
+#[derive(Debug)]
+enum Deletion {
+    Removed,
+    Staged(PathBuf),
+    /// The path is not a folder anymore, so it's left alone
+    ChangedType,
+}
+
+/// Where the answers to the confirmation prompts are read from.
+pub struct Input<'a>(Box<dyn io::BufRead + 'a>);
+
+impl fmt::Debug for Input<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Input").finish()
+    }
 }
 
 #[derive(Debug)]
@@ -43,6 +254,14 @@ where
     previous_info: Option<DirInfo>,
     wipe_info: Option<DirInfo>,
     ignore_info: Option<DirInfo>,
+    /// The totals of the pnpm stores outside of the searched folder, kept apart from its own
+    store_info: Option<DirInfo>,
+    pnpm_stores: Vec<String>,
+    folders: Vec<FolderReport>,
+    errors: Vec<ErrorReport>,
+    limiter: Arc<FsLimiter>,
+    input: Input<'a>,
+    hooks: Hooks<'a>,
 }
 
 impl<'a, W> Wipe<'a, W>
@@ -56,31 +275,247 @@ where
             previous_info: None,
             wipe_info: None,
             ignore_info: None,
+            store_info: None,
+            pnpm_stores: Vec::new(),
+            folders: Vec::new(),
+            errors: Vec::new(),
+            limiter: Arc::new(FsLimiter::new(params.max_inflight)),
+            input: Input(Box::new(io::BufReader::new(io::stdin()))),
+            hooks: Hooks::default(),
         }
     }
 
+    pub fn with_hooks(mut self, hooks: Hooks<'a>) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Reads the answers to the confirmation prompts from `input` instead of stdin.
+    pub fn with_input(mut self, input: impl io::BufRead + 'a) -> Self {
+        self.input = Input(Box::new(input));
+        self
+    }
+
     pub fn run(&mut self) -> io::Result<()> {
-        self.write_header()?;
-        self.write_content()?;
-        self.write_footer()?;
+        if let Some(projects) = self.params.benchmark {
+            return self.write_benchmark(projects);
+        }
+
+        // held until the run is over
+        let _lock = match &self.params.lock {
+            Some(path) if self.params.wipe => Some(WipeLock::acquire(path)?),
+            _ => None,
+        };
+
+        self.write_wsl_warning()?;
+
+        if self.params.events.is_some() {
+            self.write_content()?;
+            self.emit(Event::Summary(self.report().summary))?;
+        } else if self.text() {
+            self.write_header()?;
+            self.write_content()?;
+            self.write_footer()?;
+        } else {
+            self.write_content()?;
+            self.report().write(
+                self.params.format,
+                self.params.output_version,
+                self.params.size_precision.unwrap_or(2),
+                &mut self.stdout,
+            )?;
+        }
+
+        if let Some(dir) = &self.params.per_project_report {
+            per_project::write(&self.report(), dir)?;
+        }
+
+        if let Some(dir) = &self.params.shard_output {
+            shards::write(&self.report(), dir, self.params.shard_size)?;
+        }
+
+        if let Some(file) = &self.params.summary_json {
+            json::write_summary(&self.report().summary, fs::File::create(file)?)?;
+        }
 
         Ok(())
     }
 
-    fn write_header(&mut self) -> io::Result<()> {
+    /// Builds the structured representation of the run. Only valid after `write_content`.
+    pub fn report(&self) -> Report {
+        let wipe_info = self.wipe_info.expect("this should never be None");
+        let ignore_info = self.ignore_info.expect("this should never be None");
+
+        let mut summary = match self.previous_info {
+            Some(previous_info) => SummaryReport {
+                total: previous_info.into(),
+                ignored: ignore_info.into(),
+                wipe: wipe_info.into(),
+                after: DirInfo::new(
+                    previous_info.dir_count.saturating_sub(wipe_info.dir_count),
+                    previous_info
+                        .file_count
+                        .saturating_sub(wipe_info.file_count),
+                    previous_info.size.saturating_sub(wipe_info.size),
+                )
+                .into(),
+                found_bytes: wipe_info.size,
+                reclaimed_bytes: 0,
+                failed_count: 0,
+                status: Status::default(),
+                exit_code: 0,
+                lingering: None,
+                stale: None,
+                pnpm_store: self.store_info.map(Totals::from),
+            },
+            None => SummaryReport::default(),
+        };
+
         if self.params.wipe {
+            summary.reclaimed_bytes = self
+                .folders
+                .iter()
+                .filter(|folder| folder.skipped.is_none() && folder.error.is_none())
+                .filter(|folder| !self.pnpm_stores.contains(&folder.path))
+                .filter_map(|folder| folder.reclaim_bytes.or(folder.size))
+                .sum();
+        }
+        summary.failed_count = self
+            .folders
+            .iter()
+            .filter(|folder| folder.error.is_some())
+            .count();
+
+        if self.params.verify && self.params.wipe {
+            summary.lingering = Some(
+                self.folders
+                    .iter()
+                    .filter(|folder| folder.verified == Some(false))
+                    .count(),
+            );
+        }
+
+        summary.status = if summary.failed_count > 0 {
+            Status::PartialFailure
+        } else if summary.lingering.is_some_and(|lingering| lingering > 0) {
+            Status::Lingering
+        } else if self.folders.is_empty() {
+            Status::Empty
+        } else if self.params.wipe {
+            Status::Ok
+        } else {
+            Status::Found
+        };
+        summary.exit_code = summary.status.exit_code();
+
+        if self.params.wipe_from.is_some() {
+            let stale = self
+                .folders
+                .iter()
+                .filter(|folder| folder.skipped == Some(SkipReason::Stale))
+                .count();
+
+            summary.stale = Some(stale);
+        }
+
+        let mut folders = self.folders.clone();
+        for folder in &mut folders {
+            folder.settle();
+        }
+
+        // the walk order depends on the filesystem and the errors come from several threads
+        folders.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut errors = self.errors.clone();
+        errors.sort_by(|a, b| (&a.path, a.phase).cmp(&(&b.path, b.phase)));
+
+        Report {
+            path: self.params.path.display().to_string(),
+            directory: DirectoryEnum::from(self.params.language.clone()).to_string(),
+            wipe: self.params.wipe,
+            sort_applied: "path".to_string(),
+            folders,
+            summary,
+            errors,
+        }
+    }
+
+    fn write_benchmark(&mut self, projects: usize) -> io::Result<()> {
+        let parent = self
+            .params
+            .benchmark_dir
+            .clone()
+            .unwrap_or_else(env::temp_dir);
+        let path = self.printable(&parent.display().to_string()).into_owned();
+        writeln!(
+            self.stdout,
+            "{} Timing the search of {} synthetic projects in {}...",
+            Paint::green("[BENCHMARK]").bold(),
+            Paint::cyan(projects),
+            Paint::cyan(path),
+        )?;
+        self.stdout.flush()?;
+
+        let benchmark = benchmark::run(&parent, projects, Arc::clone(&self.limiter))?;
+
+        writeln!(
+            self.stdout,
+            "Searched {} folders and sized {} in {} ms: {} folders/s, {} MiB/s",
+            benchmark.dir_count,
+            self.size_flex(&DirInfo::new(0, 0, benchmark.size)),
+            benchmark.duration.as_millis(),
+            Paint::green(format!("{:.0}", benchmark.dirs_per_sec())),
+            Paint::green(format!("{:.2}", benchmark.mib_per_sec())),
+        )?;
+        self.stdout.flush()?;
+
+        Ok(())
+    }
+
+    /// The code the process exits with, after a successful `run`.
+    pub fn exit_code(&self) -> i32 {
+        // a benchmark doesn't search the current folder, so there is nothing to report
+        if self.params.benchmark.is_some() {
+            return 0;
+        }
+
+        self.report().summary.exit_code
+    }
+
+    fn write_wsl_warning(&mut self) -> io::Result<()> {
+        let proc_version = wsl::proc_version();
+
+        if let Some(warning) =
+            wsl::windows_drive_warning(&self.params.path, proc_version.as_deref())
+        {
+            // keep the structured formats parsable
+            if self.text() {
+                let warning = self.printable(&warning).into_owned();
+                writeln!(self.stdout, "{}", Paint::yellow(warning))?;
+            } else {
+                eprintln!("{warning}");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.params.wipe && self.params.stage {
+            write!(self.stdout, "{}", Paint::red("[STAGING]").bold())?;
+        } else if self.params.wipe {
             write!(self.stdout, "{}", Paint::red("[WIPING]").bold())?;
         } else {
             write!(self.stdout, "{}", Paint::green("[DRY RUN]").bold())?;
         }
 
         let directory: DirectoryEnum = self.params.language.clone().into();
+        let path = self.root_path();
 
         writeln!(
             self.stdout,
             r#" Recursively searching for all "{}" folders in {}..."#,
             Paint::cyan(&directory),
-            Paint::cyan(self.params.path.display()),
+            Paint::cyan(path),
         )?;
 
         self.stdout.flush()?;
@@ -90,30 +525,126 @@ where
 
     fn write_content(&mut self) -> io::Result<()> {
         let directory: DirectoryEnum = self.params.language.clone().into();
-        let paths_to_delete = get_paths_to_delete(&self.params.path, &directory)?;
-        let paths_to_delete = paths_to_delete
-            .iter()
-            .filter_map(|p| match p {
-                Ok(item) => Some(item),
-                _ => None,
-            })
-            .collect::<Vec<_>>();
+        let mut stale = Vec::new();
+        let paths_to_delete = if let Some(file) = &self.params.wipe_from {
+            let mut paths = Vec::new();
+            for path in json::read_paths(fs::File::open(file)?)? {
+                if is_valid_match(Path::new(&path), &directory, &self.limiter) {
+                    paths.push(Ok(path));
+                } else {
+                    stale.push(path);
+                }
+            }
 
-        if !paths_to_delete.is_empty() {
-            writeln!(self.stdout)?;
+            paths
+        } else if self.params.commit_staged {
+            get_staged_paths(
+                &self.params.path,
+                &directory,
+                DepthBand::new(self.params.min_depth, self.params.max_depth),
+                &self.params.exclude_regex,
+                &self.limiter,
+                self.params.continue_on_panic,
+            )?
+        } else {
+            let extra_names =
+                if self.params.detect_monorepo_tool && directory == DirectoryEnum::NodeModules {
+                    monorepo::cache_names(&self.params.path)
+                } else {
+                    Vec::new()
+                };
 
-            self.writeln_spaced_line(
-                Paint::cyan("Files #"),
-                Paint::cyan("Size (MB)"),
-                "",
-                Paint::cyan("Path"),
-            )?;
+            get_paths_to_delete(
+                &self.params.path,
+                &directory,
+                &extra_names,
+                DepthBand::new(self.params.min_depth, self.params.max_depth),
+                &self.params.exclude_regex,
+                &self.limiter,
+                self.params.continue_on_panic,
+            )?
+        };
+        let mut paths = Vec::new();
+        for path in paths_to_delete {
+            match path {
+                Ok(path) => paths.push(path),
+                Err(e) => self.push_error(ErrorReport {
+                    path: e.path,
+                    phase: ErrorPhase::Walk,
+                    message: e.error.to_string(),
+                    kind: e.error.kind(),
+                })?,
+            }
+        }
+
+        let mut paths_to_delete = paths;
 
-            self.previous_info = Some(dir_size(&self.params.path)?);
+        if self.params.include_pnpm_store {
+            // the stores aren't found by the search, so they are checked the way it would have
+            let stores = paths_to_delete
+                .iter()
+                .filter(|p| pnpm::is_pnpm_layout(Path::new(p)))
+                .filter_map(|p| pnpm::store_dir(Path::new(p)))
+                .filter(|store| pnpm::is_store(store, &self.limiter))
+                .filter(|store| !self.params.path.starts_with(store))
+                .filter(|store| !self.params.exclude_regex.iter().any(|r| r.is_match(store)))
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>();
+
+            for store in stores {
+                if paths_to_delete.contains(&store) {
+                    continue;
+                }
+
+                // a store inside the searched folder counts towards it, like any other folder
+                if !Path::new(&store).starts_with(&self.params.path) {
+                    self.pnpm_stores.push(store.clone());
+                }
+                paths_to_delete.push(store);
+            }
+        }
+
+        if !paths_to_delete.is_empty() || !stale.is_empty() {
+            self.previous_info = Some(dir_size(&self.params.path, &self.limiter)?);
+        }
+
+        let store_count = self.pnpm_stores.len();
+        self.wipe_info = Some(DirInfo::new(paths_to_delete.len() - store_count, 0, 0));
+        self.ignore_info = Some(DirInfo::new(0, 0, 0));
+        self.store_info = (store_count > 0).then(|| DirInfo::new(store_count, 0, 0));
+
+        // the guards and the grouped confirmations need all the sizes before anything can be
+        // deleted
+        let stream = self.params.expect_at_least.is_none()
+            && self.params.min_reclaim_ratio.is_none()
+            && self.params.fastest_growing.is_none()
+            && !self.params.coalesce_siblings;
+
+        if stream && !paths_to_delete.is_empty() {
+            self.write_table_header()?;
         }
 
-        let mut wipe_info = DirInfo::new(paths_to_delete.len(), 0, 0);
-        let mut ignore_info = DirInfo::new(0, 0, 0);
+        // the rows streamed by the text output already show the progress
+        let progress =
+            self.params.progress && !(stream && self.text()) && !paths_to_delete.is_empty();
+
+        if progress && !self.text() {
+            eprintln!(
+                "The progress is written to stderr to keep the {} output valid",
+                self.params.format
+            );
+        }
+
+        let snapshot = match &self.params.snapshot {
+            Some(file) => Some(json::read_sizes(fs::File::open(file)?)?),
+            None => None,
+        };
+
+        let denied = match &self.params.deny_file {
+            Some(file) => deny_file::read(file)?,
+            None => Default::default(),
+        };
+
         let paths_ignored = self
             .params
             .ignores
@@ -121,70 +652,729 @@ where
             .map(|p| p.display().to_string().to_lowercase())
             .collect::<Vec<_>>();
 
-        for path in paths_to_delete {
-            let dir_info = dir_size(path);
+        for (i, dir_info, size_duration, samples) in dir_sizes_ordered(
+            paths_to_delete.clone(),
+            Arc::clone(&self.limiter),
+            self.params.sample.unwrap_or_default(),
+            self.params.continue_on_panic,
+        ) {
+            let path = &paths_to_delete[i];
+
+            let dir_info = match dir_info {
+                Ok(dir_info) => Some(dir_info),
+                Err(e) => {
+                    self.push_error(ErrorReport {
+                        path: path.clone(),
+                        phase: ErrorPhase::Size,
+                        message: e.to_string(),
+                        kind: e.kind(),
+                    })?;
+
+                    None
+                }
+            };
+
+            let git_repo_root = git::repo_root(Path::new(path));
 
-            let ignored = paths_ignored
+            let skipped = if paths_ignored
                 .iter()
-                .any(|p| path.to_lowercase().starts_with(p));
-
-            if let Ok(dir_info) = dir_info {
-                self.write_spaced_line(
-                    dir_info.file_count_formatted(),
-                    dir_info.size_formatted_mb(),
-                    "",
-                    path,
-                )?;
+                .any(|p| path.to_lowercase().starts_with(p))
+            {
+                Some(SkipReason::Ignored)
+            } else if !denied.is_empty() && denied.contains(&deny_file::normalize(Path::new(path)))
+            {
+                Some(SkipReason::Denied)
+            } else if self
+                .params
+                .skip_if_contains
+                .iter()
+                .any(|sentinel| Path::new(path).join(sentinel).exists())
+            {
+                Some(SkipReason::Protected)
+            } else if self.params.exclude_if_under_git_lfs
+                && git_repo_root
+                    .as_ref()
+                    .is_some_and(|root| lfs::is_tracked(Path::new(path), root))
+            {
+                Some(SkipReason::Lfs)
+            } else if self
+                .params
+                .min_size
+                .zip(dir_info)
+                .is_some_and(|(min_size, dir_info)| dir_info.size < min_size.0)
+            {
+                Some(SkipReason::TooSmall)
+            } else {
+                None
+            };
+
+            let reclaim_bytes = dir_info
+                .filter(|_| !self.params.keep_inside.is_empty())
+                .map(|i| {
+                    let kept = kept_size(Path::new(path), &self.params.keep_inside, &self.limiter);
+                    i.size.saturating_sub(kept)
+                });
 
-                if ignored {
+            if let Some(dir_info) = dir_info {
+                if self.pnpm_stores.contains(path) {
+                    let store_info = self.store_info.as_mut().expect("this should never be None");
+                    if skipped.is_none() {
+                        store_info.file_count += dir_info.file_count;
+                        store_info.size += dir_info.size;
+                    }
+                } else if skipped.is_some() {
+                    let ignore_info = self
+                        .ignore_info
+                        .as_mut()
+                        .expect("this should never be None");
                     ignore_info.dir_count += 1;
                     ignore_info.file_count += dir_info.file_count;
                     ignore_info.size += dir_info.size;
                 } else {
+                    let wipe_info = self.wipe_info.as_mut().expect("this should never be None");
                     wipe_info.file_count += dir_info.file_count;
-                    wipe_info.size += dir_info.size;
+                    wipe_info.size += reclaim_bytes.unwrap_or(dir_info.size);
                 }
+            }
+
+            let git_repo_root = git_repo_root.map(|p| p.display().to_string());
+            let (created, modified) = if self.params.with_times {
+                times::folder_times(Path::new(path))
+            } else {
+                (None, None)
+            };
+
+            self.folders.push(FolderReport {
+                path: path.clone(),
+                matched_name: matched_name(path),
+                file_count: dir_info.map(|i| i.file_count),
+                size: dir_info.map(|i| i.size),
+                reclaim_bytes,
+                skipped,
+                error: None,
+                reclaimable: false,
+                skip_reason: None,
+                staged: None,
+                size_duration_ms: self
+                    .params
+                    .timings_per_folder
+                    .then_some(size_duration.as_millis()),
+                path_components: self.params.path_components.then(|| path_components(path)),
+                size_human: dir_info
+                    .filter(|_| self.params.size_precision.is_some())
+                    .map(|i| self.size_flex(&i)),
+                shallow_size: dir_info
+                    .filter(|_| self.params.with_shallow)
+                    .map(|i| i.shallow_size),
+                symlink_count: dir_info
+                    .filter(|_| self.params.show_symlinks)
+                    .map(|i| i.symlink_count),
+                in_git_repo: git_repo_root.is_some(),
+                git_repo_root,
+                rebuild_count: if self.params.rebuild_cost && directory == DirectoryEnum::Target {
+                    cargo_lock::rebuild_count(Path::new(path))
+                } else {
+                    None
+                },
+                created,
+                modified,
+                volume: if self.params.with_volume {
+                    volume::volume(Path::new(path))
+                } else {
+                    None
+                },
+                growth: snapshot.as_ref().zip(dir_info).map(|(snapshot, dir_info)| {
+                    dir_info.size as i64 - snapshot.get(path).copied().unwrap_or_default() as i64
+                }),
+                verified: None,
+                samples: self.params.sample.map(|_| samples),
+                still_present: self.params.wipe_from.is_some().then_some(true),
+                still_valid: self.params.wipe_from.is_some().then_some(true),
+            });
+            self.folders[i].settle();
+            self.emit(Event::Found(Box::new(self.folders[i].clone())))?;
+
+            if stream {
+                self.write_folder(i)?;
+            }
+
+            if progress {
+                self.write_progress(i + 1, paths_to_delete.len())?;
+            }
+        }
+
+        if progress {
+            if self.text() {
+                writeln!(self.stdout)?;
+            } else {
+                eprintln!();
+            }
+        }
+
+        for path in stale {
+            let still_present = Path::new(&path).exists();
+
+            self.folders.push(FolderReport {
+                matched_name: matched_name(&path),
+                path,
+                skipped: Some(SkipReason::Stale),
+                still_present: Some(still_present),
+                still_valid: Some(false),
+                ..FolderReport::default()
+            });
+            let i = self.folders.len() - 1;
+            self.folders[i].settle();
+            self.emit(Event::Found(Box::new(self.folders[i].clone())))?;
+
+            if stream {
+                self.write_folder(i)?;
+            }
+        }
+
+        if let Some(fastest_growing) = self.params.fastest_growing {
+            self.skip_slower_growth(fastest_growing);
+        }
+
+        self.check_expect_at_least()?;
+        self.check_min_reclaim_ratio()?;
+
+        if self.params.coalesce_siblings {
+            self.confirm_siblings()?;
+        }
+
+        if !stream {
+            if !self.folders.is_empty() {
+                self.write_table_header()?;
+            }
+
+            for i in 0..self.folders.len() {
+                self.write_folder(i)?;
+            }
+        }
+
+        let hidden = self.folders.len().saturating_sub(self.head());
+        if self.text() && hidden > 0 {
+            writeln!(self.stdout, "... and {hidden} more (use --all to show)")?;
+        }
+
+        if self.text() {
+            self.write_walk_errors()?;
+        }
+
+        if self.params.verify && self.params.wipe {
+            self.verify()?;
+        }
+
+        Ok(())
+    }
+
+    /// Sibling folders, such as the `target` folders of the crates of a workspace, are summed up
+    /// under their common parent and confirmed all at once.
+    fn confirm_siblings(&mut self) -> io::Result<()> {
+        let directory: DirectoryEnum = self.params.language.clone().into();
+        let text = self.text();
+
+        let mut groups: Vec<(PathBuf, Vec<usize>)> = Vec::new();
+        for (i, folder) in self.folders.iter().enumerate() {
+            if folder.skipped.is_some() {
+                continue;
+            }
+
+            let path = Path::new(&folder.path);
+            let parent = path
+                .parent()
+                .and_then(Path::parent)
+                .or_else(|| path.parent())
+                .unwrap_or(path)
+                .to_path_buf();
+
+            match groups.iter_mut().find(|(p, _)| *p == parent) {
+                Some((_, indices)) => indices.push(i),
+                None => groups.push((parent, vec![i])),
+            }
+        }
+
+        if text && !groups.is_empty() {
+            writeln!(self.stdout)?;
+        }
+
+        for (parent, indices) in groups {
+            let size = indices
+                .iter()
+                .filter_map(|&i| self.folders[i].size)
+                .sum::<usize>();
+            let parent = self.printable(&parent.display().to_string()).into_owned();
+            let summary = format!(
+                r#"{} "{}" folders under {}, {} total"#,
+                indices.len(),
+                Paint::cyan(&directory),
+                Paint::cyan(parent),
+                Paint::red(self.size_flex(&DirInfo::new(0, 0, size))),
+            );
+
+            if !self.params.wipe {
+                if text {
+                    writeln!(self.stdout, "{summary}")?;
+                }
+                continue;
+            }
+
+            let choices = match self.params.prompt_default {
+                PromptDefaultEnum::Yes => "[Y/n]",
+                PromptDefaultEnum::No => "[y/N]",
+            };
+
+            if text {
+                write!(self.stdout, "{summary}, delete all? {choices} ")?;
+                self.stdout.flush()?;
             } else {
-                self.write_spaced_line("?", "?", "", path)?;
+                // keep the structured formats parsable
+                eprint!("{summary}, delete all? {choices} ");
+            }
+
+            if self.confirmed()? {
+                continue;
+            }
+
+            for i in indices {
+                self.skip(i, SkipReason::Declined);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Skips all but the `fastest_growing` folders that grew the most since the snapshot.
+    fn skip_slower_growth(&mut self, fastest_growing: usize) {
+        let mut growing = self
+            .folders
+            .iter()
+            .enumerate()
+            .filter(|(_, folder)| folder.skipped.is_none())
+            .map(|(i, folder)| (i, folder.growth.unwrap_or(i64::MIN)))
+            .collect::<Vec<_>>();
+        growing.sort_by_key(|&(i, growth)| (std::cmp::Reverse(growth), i));
+
+        for &(i, _) in growing.iter().skip(fastest_growing) {
+            self.skip(i, SkipReason::SlowerGrowth);
+        }
+    }
+
+    /// Reads the answer to a confirmation prompt. Only an empty line takes the default, running
+    /// out of input always declines.
+    fn confirmed(&mut self) -> io::Result<bool> {
+        let mut answer = String::new();
+        let read = self.input.0.read_line(&mut answer)?;
+
+        Ok(match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => true,
+            "" if read > 0 => self.params.prompt_default == PromptDefaultEnum::Yes,
+            _ => false,
+        })
+    }
+
+    /// Moves a folder that is not going to be wiped after all from the wipe totals to the
+    /// skipped ones.
+    fn skip(&mut self, i: usize, reason: SkipReason) {
+        let folder = &mut self.folders[i];
+        folder.skipped = Some(reason);
+
+        if self.pnpm_stores.contains(&folder.path) {
+            let store_info = self.store_info.as_mut().expect("this should never be None");
+
+            if let (Some(file_count), Some(size)) = (folder.file_count, folder.size) {
+                store_info.file_count -= file_count;
+                store_info.size -= size;
+            }
+
+            return;
+        }
+
+        let wipe_info = self.wipe_info.as_mut().expect("this should never be None");
+        let ignore_info = self
+            .ignore_info
+            .as_mut()
+            .expect("this should never be None");
+
+        ignore_info.dir_count += 1;
+
+        if let (Some(file_count), Some(size)) = (folder.file_count, folder.size) {
+            wipe_info.file_count -= file_count;
+            // with `--keep-inside` only what gets wiped was counted
+            wipe_info.size -= folder.reclaim_bytes.unwrap_or(size);
+            ignore_info.file_count += file_count;
+            ignore_info.size += size;
+        }
+    }
+
+    fn write_table_header(&mut self) -> io::Result<()> {
+        if self.text() {
+            writeln!(self.stdout)?;
+
+            let symlinks = self.symlinks_column(Paint::cyan("Symlinks"));
+
+            self.writeln_spaced_line(
+                Paint::cyan("Files #"),
+                Paint::cyan(if self.params.auto_units {
+                    "Size"
+                } else {
+                    "Size (MB)"
+                }),
+                symlinks,
+                Paint::cyan("Path"),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the row of an already sized folder and wipes it, unless it is skipped.
+    fn write_folder(&mut self, i: usize) -> io::Result<()> {
+        let text = self.text() && i < self.head();
+        let folder = self.folders[i].clone();
+        let path = self.printable(&folder.path).into_owned();
+
+        if text {
+            match (folder.file_count, folder.size) {
+                (Some(file_count), Some(size)) => {
+                    let dir_info = DirInfo::new(0, file_count, size);
+                    let symlinks = self.symlinks_column(
+                        folder
+                            .symlink_count
+                            .unwrap_or_default()
+                            .to_formatted_string(&Locale::en),
+                    );
+
+                    let size = if self.params.auto_units {
+                        self.size_flex(&dir_info)
+                    } else {
+                        dir_info.size_formatted_mb()
+                    };
+
+                    self.write_spaced_line(dir_info.file_count_formatted(), size, symlinks, &path)?;
+                }
+                _ => {
+                    let symlinks = self.symlinks_column("?");
+                    self.write_spaced_line("?", "?", symlinks, &path)?
+                }
             }
 
-            if ignored {
-                write!(self.stdout, " {}", Paint::yellow("[Ignored]"))?;
-            } else if self.params.wipe {
-                let r = fs::remove_dir_all(path);
+            if let Some(size_duration_ms) = folder.size_duration_ms {
+                write!(
+                    self.stdout,
+                    " {}",
+                    Paint::default(format!("({size_duration_ms} ms)")).dimmed()
+                )?;
+            }
+
+            if let Some(rebuild_count) = folder.rebuild_count {
+                let note = format!("(≈ {rebuild_count} dependencies will recompile)");
+                let note = self.printable(&note).into_owned();
+                write!(self.stdout, " {}", Paint::default(note).dimmed())?;
+            }
+        }
 
-                if let Err(e) = r {
-                    write!(self.stdout, " {}", Paint::red(&format!("[{e}]")))?;
+        if let Some(skipped) = folder.skipped {
+            if text {
+                let label = match skipped {
+                    SkipReason::Ignored => "[Ignored]",
+                    SkipReason::Protected => "[Protected]",
+                    SkipReason::Declined => "[Declined]",
+                    SkipReason::Lfs => "[Git LFS]",
+                    SkipReason::Stale => "[Stale]",
+                    SkipReason::ChangedType => "[Path changed type, skipping]",
+                    SkipReason::TooSmall => "[Too small]",
+                    SkipReason::SlowerGrowth => "[Slower growth]",
+                    SkipReason::Denied => "[Denied]",
+                };
+
+                write!(self.stdout, " {}", Paint::yellow(label))?;
+            }
+        } else if self.params.wipe {
+            match self.delete(&folder.path) {
+                Ok(Deletion::Staged(staged)) => {
+                    if text {
+                        let name = staged.file_name().unwrap_or_default().to_string_lossy();
+                        let name = self.printable(&name).into_owned();
+                        write!(
+                            self.stdout,
+                            " {}",
+                            Paint::cyan(format!("[Staged as {name}]"))
+                        )?;
+                    }
+
+                    self.folders[i].staged = Some(staged.display().to_string());
+                    self.emit(Event::Deleted {
+                        path: folder.path.clone(),
+                        staged: self.folders[i].staged.clone(),
+                    })?;
+                }
+                Ok(Deletion::Removed) => self.emit(Event::Deleted {
+                    path: folder.path.clone(),
+                    staged: None,
+                })?,
+                Ok(Deletion::ChangedType) => {
+                    if text {
+                        write!(
+                            self.stdout,
+                            " {}",
+                            Paint::yellow("[Path changed type, skipping]")
+                        )?;
+                    }
+
+                    self.skip(i, SkipReason::ChangedType);
+                }
+                Err(e) => {
+                    if text {
+                        let message = self.printable(&e.to_string()).into_owned();
+                        write!(self.stdout, " {}", Paint::red(&format!("[{message}]")))?;
+                    }
+
+                    self.folders[i].error = Some(e.to_string());
+                    self.push_error(ErrorReport {
+                        path: folder.path.clone(),
+                        phase: ErrorPhase::Delete,
+                        message: e.to_string(),
+                        kind: e.kind(),
+                    })?;
                 }
             }
+        }
 
+        if text {
             writeln!(self.stdout)?;
 
-            self.stdout.flush()?;
+            // buffered output is only flushed at the end of each section
+            if !self.params.buffered {
+                self.stdout.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wipes the folder, or only renames it when staging.
+    fn delete(&mut self, path: &str) -> io::Result<Deletion> {
+        if let Some(before_delete) = &mut self.hooks.before_delete {
+            before_delete(Path::new(path));
         }
 
-        self.wipe_info = Some(wipe_info);
-        self.ignore_info = Some(ignore_info);
+        // the folder may have been replaced since it was found
+        match fs::symlink_metadata(path) {
+            Ok(data) if !data.is_dir() => return Ok(Deletion::ChangedType),
+            _ => (),
+        }
+
+        if let Some(manifest_dir) = &self.params.manifest {
+            write_manifest(manifest_dir, Path::new(path), &self.limiter)?;
+        }
+
+        if self.params.stage {
+            let staged = stage_path(path)?;
+            self.after_delete(path);
+
+            return Ok(Deletion::Staged(staged));
+        }
+
+        let keep_inside = &self.params.keep_inside;
+        if keep_inside
+            .iter()
+            .any(|kept| Path::new(path).join(kept).exists())
+        {
+            remove_dir_except(Path::new(path), keep_inside)?;
+            self.after_delete(path);
+
+            return Ok(Deletion::Removed);
+        }
+
+        let removed = if self.params.atomic {
+            // a folder that can't be renamed, e.g. across devices, is wiped in place instead
+            rename_aside(path).unwrap_or_else(|_| PathBuf::from(path))
+        } else {
+            PathBuf::from(path)
+        };
+
+        if let Some(before_remove) = &mut self.hooks.before_remove {
+            before_remove(&removed);
+        }
+
+        if let Err(e) = remove_dir(&removed, self.params.chmod_parents) {
+            // what is left of a folder renamed aside is put back, so that it can still be found
+            if removed != Path::new(path) && fs::rename(&removed, path).is_err() {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("{e}, what is left of it is in {}", removed.display()),
+                ));
+            }
+
+            return Err(e);
+        }
+        self.after_delete(path);
+
+        Ok(Deletion::Removed)
+    }
+
+    fn after_delete(&mut self, path: &str) {
+        if let Some(after_delete) = &mut self.hooks.after_delete {
+            after_delete(Path::new(path));
+        }
+    }
+
+    /// The folders that could not be searched, one per line or collapsed by kind with
+    /// `--concise-errors`, in which case `--verbose` still lists them under their kind.
+    fn write_walk_errors(&mut self) -> io::Result<()> {
+        let errors = self.errors.clone();
+        let groups = group_walk_errors(&errors);
+
+        if groups.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.stdout)?;
+
+        if !self.params.concise_errors {
+            for error in groups.iter().flat_map(|group| &group.errors) {
+                let message = self.printable(&error.message).into_owned();
+                let path = self.printable(&error.path).into_owned();
+                writeln!(self.stdout, "{} {path}", Paint::red(format!("[{message}]")))?;
+            }
+
+            return Ok(());
+        }
+
+        for group in &groups {
+            writeln!(self.stdout, "{}", Paint::red(group))?;
+
+            if self.params.verbose {
+                for error in &group.errors {
+                    let path = self.printable(&error.path).into_owned();
+                    writeln!(self.stdout, "{:SPACING_FILES$}{path}", "")?;
+                }
+            }
+        }
 
         Ok(())
     }
 
+    /// Checks that the wiped folders are really gone, since something running at the same time
+    /// could have created them again.
+    fn verify(&mut self) -> io::Result<()> {
+        let mut lingering = Vec::new();
+
+        for folder in &mut self.folders {
+            if folder.skipped.is_some() || folder.error.is_some() {
+                continue;
+            }
+
+            let path = Path::new(&folder.path);
+            let gone = !path.exists()
+                || (!self.params.keep_inside.is_empty()
+                    && !has_leftovers(path, &self.params.keep_inside)?);
+            folder.verified = Some(gone);
+
+            if !gone {
+                lingering.push(folder.path.clone());
+            }
+        }
+
+        if self.text() && !lingering.is_empty() {
+            writeln!(self.stdout)?;
+            writeln!(
+                self.stdout,
+                "{}",
+                Paint::red(format!(
+                    "{} of the wiped folders are still present:",
+                    lingering.len()
+                ))
+            )?;
+
+            for path in lingering {
+                let path = self.printable(&path).into_owned();
+                writeln!(self.stdout, "{:SPACING_FILES$}{path}", "")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Overwrites the previous progress line, which is never written to the stdout of the
+    /// structured formats.
+    fn write_progress(&mut self, sized: usize, total: usize) -> io::Result<()> {
+        let line = format!("\rSized {sized} of {total} folders");
+
+        if self.text() {
+            write!(self.stdout, "{line}")?;
+            self.stdout.flush()?;
+        } else {
+            eprint!("{line}");
+        }
+
+        Ok(())
+    }
+
+    fn check_expect_at_least(&self) -> io::Result<()> {
+        let wipe_info = self.wipe_info.expect("this should never be None");
+
+        match self.params.expect_at_least {
+            Some(expected) if wipe_info.size < expected.0 => Err(io::Error::other(format!(
+                "Only {} can be wiped, but at least {} was expected",
+                self.size_flex(&wipe_info),
+                self.size_flex(&DirInfo::new(0, 0, expected.0)),
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_min_reclaim_ratio(&self) -> io::Result<()> {
+        let Some(min_ratio) = self.params.min_reclaim_ratio else {
+            return Ok(());
+        };
+
+        // only the wipe is refused, a dry run still lists what would be wiped
+        if !self.params.wipe {
+            return Ok(());
+        }
+
+        let wipe_info = self.wipe_info.expect("this should never be None");
+        let free = match &self.hooks.free_space {
+            Some(free_space) => free_space(&self.params.path)?,
+            None => free_space::available(&self.params.path)?,
+        };
+
+        if free_space::is_worth_it(wipe_info.size, free, min_ratio) {
+            return Ok(());
+        }
+
+        Err(io::Error::other(format!(
+            "Only {} can be wiped, which is less than {:.2}% of the {} free on the volume",
+            self.size_flex(&wipe_info),
+            min_ratio * 100.0,
+            self.size_flex(&DirInfo::new(0, 0, free)),
+        )))
+    }
+
     fn write_summary(&mut self) -> io::Result<()> {
         let previous_info = self.previous_info.expect("this should never be None");
         let wipe_info = self.wipe_info.expect("this should never be None");
         let ignore_info = self.ignore_info.expect("this should never be None");
 
         let after = DirInfo {
-            dir_count: previous_info.dir_count - wipe_info.dir_count,
-            file_count: previous_info.file_count - wipe_info.file_count,
-            size: previous_info.size - wipe_info.size,
+            dir_count: previous_info.dir_count.saturating_sub(wipe_info.dir_count),
+            file_count: previous_info
+                .file_count
+                .saturating_sub(wipe_info.file_count),
+            size: previous_info.size.saturating_sub(wipe_info.size),
+            shallow_size: 0,
+            symlink_count: 0,
         };
 
+        let path = self.root_path();
+
         self.writeln_spaced_line(
             Paint::cyan("Files #"),
             Paint::cyan("Size"),
             "",
-            Paint::cyan(self.params.path.display()),
+            Paint::cyan(&path),
         )?;
 
         let label = if self.params.wipe {
@@ -195,7 +1385,7 @@ where
 
         self.writeln_spaced_line(
             Paint::default(previous_info.file_count_formatted()),
-            Paint::default(previous_info.size_formatted_flex()),
+            Paint::default(self.size_flex(&previous_info)),
             "",
             Paint::default(label),
         )?;
@@ -203,7 +1393,7 @@ where
         if ignore_info.dir_count > 0 {
             self.writeln_spaced_line(
                 Paint::yellow(ignore_info.file_count_formatted()),
-                Paint::yellow(ignore_info.size_formatted_flex()),
+                Paint::yellow(self.size_flex(&ignore_info)),
                 "",
                 Paint::yellow("Ignored"),
             )?;
@@ -217,7 +1407,7 @@ where
 
         self.writeln_spaced_line(
             Paint::red(wipe_info.file_count_formatted()),
-            Paint::red(wipe_info.size_formatted_flex()),
+            Paint::red(self.size_flex(&wipe_info)),
             "",
             Paint::red(label),
         )?;
@@ -230,13 +1420,55 @@ where
 
         self.writeln_spaced_line(
             Paint::green(after.file_count_formatted()),
-            Paint::green(after.size_formatted_flex()),
+            Paint::green(self.size_flex(&after)),
             "",
             Paint::green(label),
         )?;
 
+        if let Some(store_info) = self.store_info {
+            let label = if self.params.wipe {
+                "Wiped from the pnpm store"
+            } else {
+                "Can wipe from the pnpm store"
+            };
+
+            self.writeln_spaced_line(
+                Paint::red(store_info.file_count_formatted()),
+                Paint::red(self.size_flex(&store_info)),
+                "",
+                Paint::red(label),
+            )?;
+        }
+
         writeln!(self.stdout)?;
 
+        if self.params.with_root_size {
+            let fraction = if previous_info.size > 0 {
+                wipe_info.size as f64 / previous_info.size as f64 * 100.0
+            } else {
+                0.0
+            };
+            let verb = if self.params.wipe { "were" } else { "are" };
+
+            writeln!(
+                self.stdout,
+                "The folders to wipe {verb} {} of the {} in {}",
+                Paint::red(format!("{fraction:.2}%")),
+                Paint::cyan(self.size_flex(&previous_info)),
+                Paint::cyan(path),
+            )?;
+            writeln!(self.stdout)?;
+        }
+
+        if let Some(stale) = self.report().summary.stale {
+            writeln!(
+                self.stdout,
+                "{} of the saved folders were stale and left alone",
+                Paint::yellow(stale),
+            )?;
+            writeln!(self.stdout)?;
+        }
+
         self.stdout.flush()?;
 
         Ok(())
@@ -250,11 +1482,18 @@ where
         if wipe_info.dir_count > 0 {
             self.write_summary()?;
 
-            if !self.params.wipe {
+            if self.params.wipe_unconfirmed {
+                writeln!(
+                    self.stdout,
+                    "Nothing was wiped, but all folders found would be. Run {} to actually wipe them. {}",
+                    Paint::red(format!("cargo wipe {} -w --force", self.params.language)),
+                    Paint::red("USE WITH CAUTION!")
+                )?;
+            } else if !self.params.wipe {
                 writeln!(
                     self.stdout,
                     "Run {} to wipe all folders found. {}",
-                    Paint::red(format!("cargo wipe {} -w", self.params.language)),
+                    Paint::red(format!("cargo wipe {} -w --force", self.params.language)),
                     Paint::red("USE WITH CAUTION!")
                 )?;
             } else {
@@ -269,6 +1508,58 @@ where
         Ok(())
     }
 
+    /// The regular text output, which the structured formats and the events replace.
+    fn text(&self) -> bool {
+        !self.params.structured()
+    }
+
+    /// How many rows are shown.
+    fn head(&self) -> usize {
+        self.params.head.unwrap_or(usize::MAX)
+    }
+
+    fn emit(&mut self, event: Event) -> io::Result<()> {
+        match self.params.events {
+            Some(EventsEnum::Ndjson) => events::write_ndjson(&event, &mut self.stdout),
+            None => Ok(()),
+        }
+    }
+
+    fn push_error(&mut self, error: ErrorReport) -> io::Result<()> {
+        self.emit(Event::Error(error.clone()))?;
+        self.errors.push(error);
+
+        Ok(())
+    }
+
+    /// Paths and error messages come from the system, so they are the only text that may not be
+    /// ASCII already.
+    fn printable<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        if self.params.ascii {
+            to_ascii(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    fn size_flex(&self, dir_info: &DirInfo) -> String {
+        dir_info.size_formatted_flex_precision(self.params.size_precision.unwrap_or(2))
+    }
+
+    /// The symlinks column goes between the size and the path, when it is shown.
+    fn symlinks_column(&self, value: impl Display) -> String {
+        if self.params.show_symlinks {
+            format!("{value:>SPACING_SYMLINKS$}{:SPACING_PATH$}", "")
+        } else {
+            String::new()
+        }
+    }
+
+    fn root_path(&self) -> String {
+        self.printable(&self.params.path.display().to_string())
+            .into_owned()
+    }
+
     fn write_spaced_line(
         &mut self,
         column_1: impl Display,
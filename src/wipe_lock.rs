@@ -0,0 +1,32 @@
+use std::fs::{self, TryLockError};
+use std::io;
+use std::path::Path;
+
+/// An advisory lock that keeps concurrent runs from wiping at the same time. The operating
+/// system releases it when the process exits, even when it's interrupted with Ctrl-C.
+#[derive(Debug)]
+pub struct WipeLock {
+    _file: fs::File,
+}
+
+impl WipeLock {
+    pub fn acquire(path: &Path) -> io::Result<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+
+        match file.try_lock() {
+            Ok(()) => Ok(Self { _file: file }),
+            Err(TryLockError::WouldBlock) => Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                format!(
+                    "another wipe is in progress, it holds the lock on {}",
+                    path.display()
+                ),
+            )),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::{Component, Path};
+
+/// Reads the kernel version string, which is where WSL identifies itself.
+pub fn proc_version() -> Option<String> {
+    fs::read_to_string("/proc/version").ok()
+}
+
+pub fn is_wsl(proc_version: &str) -> bool {
+    let proc_version = proc_version.to_lowercase();
+
+    proc_version.contains("microsoft") || proc_version.contains("wsl")
+}
+
+/// Returns the drive letter when `path` is one of the `/mnt/<drive>` mounts WSL uses for the
+/// Windows drives.
+pub fn windows_drive(path: &Path) -> Option<char> {
+    let mut components = path.components();
+
+    if components.next() != Some(Component::RootDir) {
+        return None;
+    }
+
+    if components.next() != Some(Component::Normal("mnt".as_ref())) {
+        return None;
+    }
+
+    match components.next()?.as_os_str().to_str()? {
+        drive if drive.len() == 1 => drive.chars().next().filter(char::is_ascii_alphabetic),
+        _ => None,
+    }
+}
+
+/// Windows drives are mounted into WSL over 9P, which makes walking them very slow.
+pub fn windows_drive_warning(path: &Path, proc_version: Option<&str>) -> Option<String> {
+    if !is_wsl(proc_version?) {
+        return None;
+    }
+
+    let drive = windows_drive(path)?;
+
+    Some(format!(
+        "{} is a Windows drive mounted into WSL, which is very slow to search. \
+        Running the Windows version of cargo-wipe from {}:\\ instead is a lot faster.",
+        path.display(),
+        drive.to_ascii_uppercase(),
+    ))
+}